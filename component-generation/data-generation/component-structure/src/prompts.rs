@@ -30,7 +30,7 @@ You always follow this response format:
 ```html
 <!-- HTML for the top level UI which should contain each component you described in step 2 -->
 ```
-4) What is the HTML for each component? Components may render child elements with the special `{children}` placeholder.
+4) What is the HTML for each component? Components may render child elements with the special `{children}` placeholder. Components with more than one area for child elements, like a layout with a header and a sidebar, may also render named slots with `{name_slot}` placeholders. Eg. `<header>{header_slot}</header>`. A slot placeholder must be the only text in its element. Fill a slot by adding a `slot` attribute to a child element of the component. Eg. `<Layout><nav slot="header">...</nav><p>Main content</p></Layout>`.
 Show each component in this format:
 - ComponentName (Standalone or Takes Children):
 ```html
@@ -71,7 +71,8 @@ fn main() {
     for (prompt, validated_responses) in &combined {
         let mut examples = HashSet::new();
         for validated_response in validated_responses {
-            let training_example = TrainingExample::new(prompt.to_string(), validated_response.clone());
+            let training_example =
+                TrainingExample::new(prompt.to_string(), validated_response.clone());
             examples.insert(training_example);
         }
         for example in examples {
@@ -514,14 +515,23 @@ impl Component {
         let description = description.description.trim().to_string();
         let html = normalize_html(&html.html)?;
 
-        // If it says it's a standalone component, make sure it doesn't contain {children}
+        // Slots like {header_slot} are filled with elements, so they must be the only text in their element
+        let slot_regex = regex::Regex::new(r"\{\w+_slot\}").unwrap();
+        let whole_slot_regex = regex::Regex::new(r">\s*\{\w+_slot\}\s*<").unwrap();
+        let slots = slot_regex.find_iter(&html).count();
+        if whole_slot_regex.find_iter(&html).count() != slots {
+            return None;
+        }
+        let takes_children = html.contains("{children}") || slots > 0;
+
+        // If it says it's a standalone component, make sure it doesn't contain {children} or any slots
         if is_standalone {
-            if html.contains("{children}") {
+            if takes_children {
                 return None;
             }
         }
-        // If it says it's not a standalone component, make sure it contains {children}
-        else if !html.contains("{children}") {
+        // If it says it's not a standalone component, make sure it contains {children} or a slot
+        else if !takes_children {
             return None;
         }
 
@@ -0,0 +1,43 @@
+use component_generation::{GenerationConfig, KalosmBackend, UiGenerator};
+
+// Generate a UI for every prompt in a JSONL file:
+// cargo run --example batch -- prompts.jsonl results.jsonl [--config <file>]
+// Each line of the prompt file is a JSON string or an object like `{"id": "login", "prompt": "a login page"}`. Rerun the same command to resume a batch that was stopped.
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut paths = Vec::new();
+    let mut config_path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => config_path = args.next(),
+            _ => paths.push(arg),
+        }
+    }
+    let [prompts, results] = paths.as_slice() else {
+        eprintln!("Usage: batch <prompts.jsonl> <results.jsonl> [--config <file>]");
+        std::process::exit(1);
+    };
+
+    let backend = match &config_path {
+        Some(path) => {
+            let config = GenerationConfig::from_toml_file(path).unwrap();
+            KalosmBackend::from_config(config).await.unwrap()
+        }
+        None => KalosmBackend::default_model().await.unwrap(),
+    };
+    let generator = UiGenerator::new(backend).with_repair_attempts(2);
+
+    let start_timestamp = std::time::Instant::now();
+    match generator.generate_batch(prompts, results).await {
+        Ok(summary) => println!(
+            "Generated {} UIs, skipped {} with earlier results and failed {}",
+            summary.generated, summary.skipped, summary.failed
+        ),
+        Err(err) => {
+            eprintln!("Failed to run the batch: {err}");
+            std::process::exit(1);
+        }
+    }
+    println!("Took: {:?}", start_timestamp.elapsed());
+}
@@ -1,5 +1,9 @@
-use component_generation::generate_ui;
+use component_generation::{
+    ChatTemplate, ComponentRegistry, DioxusVersion, GenerationConfig, GenerationEvent,
+    KalosmBackend, UiGenerator,
+};
 use kalosm::language::*;
+use std::io::Write;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Style, ThemeSet};
 use syntect::parsing::SyntaxSet;
@@ -7,16 +11,184 @@ use syntect::util::{as_24_bit_terminal_escaped, LinesWithEndings};
 
 #[tokio::main]
 async fn main() {
+    // Pass `--out <dir>` to write each generated UI to a cargo project in that directory
+    // Pass `--registry <file>` to reuse components from earlier runs and save the valid components from this run
+    // Pass `--config <file>` to load the model and sampling parameters from a TOML file
+    // Pass `--preview <file>` to write a static HTML preview of each generated UI to that file
+    // Pass `--dioxus 0.4` to write code for dioxus 0.4 instead of 0.5
+    // Pass `--gallery <dir>` to write a cargo project that renders each component of the generated UI with sample props to that directory
+    // Pass `--chat-template <llama3|chatml|phi3|mistral>` to prompt a model fine-tuned with another chat format, and `--system <prompt>` to add a system prompt
+    let mut args = std::env::args().skip(1);
+    let mut out_dir = None;
+    let mut registry_path = None;
+    let mut config_path = None;
+    let mut preview_path = None;
+    let mut gallery_dir = None;
+    let mut target = DioxusVersion::default();
+    let mut chat_template = ChatTemplate::default();
+    let mut system_prompt = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out_dir = args.next().map(std::path::PathBuf::from),
+            "--registry" => registry_path = args.next().map(std::path::PathBuf::from),
+            "--config" => config_path = args.next().map(std::path::PathBuf::from),
+            "--preview" => preview_path = args.next().map(std::path::PathBuf::from),
+            "--gallery" => gallery_dir = args.next().map(std::path::PathBuf::from),
+            "--dioxus" => {
+                target = match args.next().as_deref() {
+                    Some("0.4") => DioxusVersion::V0_4,
+                    _ => DioxusVersion::V0_5,
+                }
+            }
+            "--chat-template" => {
+                chat_template = match args.next().as_deref() {
+                    Some("chatml") => ChatTemplate::ChatMl,
+                    Some("phi3") => ChatTemplate::Phi3,
+                    Some("mistral") => ChatTemplate::Mistral,
+                    _ => ChatTemplate::Llama3,
+                }
+            }
+            "--system" => system_prompt = args.next(),
+            _ => {}
+        }
+    }
+
+    let registry = match &registry_path {
+        Some(path) => ComponentRegistry::load(path).unwrap(),
+        None => ComponentRegistry::new(),
+    };
+    let backend = match &config_path {
+        Some(path) => {
+            let config = GenerationConfig::from_toml_file(path).unwrap();
+            KalosmBackend::from_config(config).await.unwrap()
+        }
+        None => KalosmBackend::default_model().await.unwrap(),
+    };
+    let mut generator = UiGenerator::new(backend)
+        .with_repair_attempts(2)
+        .with_registry(registry)
+        .with_target(target)
+        .with_chat_template(chat_template);
+    if let Some(system_prompt) = system_prompt {
+        generator = generator.with_system_prompt(system_prompt);
+    }
+
     loop {
         let input = prompt_input("What do you want to make? ").unwrap();
         let start_timestamp = std::time::Instant::now();
-        let state = generate_ui(&input).await;
 
-        let app = state.app_component();
-        print_component(&app);
+        let mut events = Box::pin(generator.stream(&input));
+        let mut declared_components = false;
+        let mut state = None;
+        while let Some(event) = events.next().await {
+            let event = match event {
+                Ok(event) => event,
+                Err(err) => {
+                    println!("\nFailed to generate the UI: {err}");
+                    break;
+                }
+            };
+            match event {
+                GenerationEvent::Description(text) => {
+                    print!("{}", text);
+                    std::io::stdout().flush().unwrap();
+                }
+                GenerationEvent::ComponentDeclared { name, description } => {
+                    if !declared_components {
+                        println!("\nI think I will need components for this...");
+                        declared_components = true;
+                    }
+                    println!("- {} ({})", name, description);
+                }
+                GenerationEvent::HtmlComplete { .. } => {}
+                GenerationEvent::ComponentHtmlComplete { name, .. } => {
+                    println!("Created HTML for {}", name);
+                }
+                GenerationEvent::ComponentRepaired {
+                    name, diagnostics, ..
+                } => {
+                    if !diagnostics.iter().any(|diagnostic| diagnostic.is_error()) {
+                        println!("Repaired {}", name);
+                    } else {
+                        println!("Failed to repair {}", name);
+                    }
+                }
+                GenerationEvent::Finished(finished) => state = Some(finished),
+            }
+        }
+        drop(events);
+        let Some(mut state) = state else {
+            continue;
+        };
+
+        for problem in state.fix_classes() {
+            println!("{problem}");
+        }
+
+        match state.app_component() {
+            Ok(app) => print_component(&app),
+            Err(err) => println!("Failed to convert the app: {err}"),
+        }
+
+        for diagnostic in state.app_diagnostics() {
+            println!("app: {diagnostic}");
+        }
 
         for component in state.components() {
-            print_component(&component.component_string());
+            match component.component_string_with(state.components()) {
+                Ok(component) => print_component(&component),
+                Err(err) => println!("Failed to convert {}: {err}", component.name),
+            }
+            for diagnostic in component.diagnostics() {
+                println!("{}: {diagnostic}", component.name);
+            }
+        }
+
+        if let Some(registry_path) = &registry_path {
+            let registry = generator.registry_mut();
+            for component in state.components() {
+                if component.is_valid() && registry.get(&component.name).is_none() {
+                    if let Err(err) = registry.insert(component) {
+                        println!("Failed to save {}: {err}", component.name);
+                    }
+                }
+            }
+            if let Err(err) = registry.save(registry_path) {
+                println!("Failed to save the registry: {err}");
+            }
+        }
+
+        if let Some(preview_path) = &preview_path {
+            match state.write_preview(preview_path) {
+                Ok(()) => println!("Wrote the preview to {}", preview_path.display()),
+                Err(err) => println!("Failed to write the preview: {err}"),
+            }
+        }
+
+        if let Some(out_dir) = &out_dir {
+            match state.project(&input) {
+                Ok(project) => {
+                    let path = out_dir.join(&project.name);
+                    match project.write_to(&path) {
+                        Ok(()) => println!("Wrote the project to {}", path.display()),
+                        Err(err) => println!("Failed to write the project: {err}"),
+                    }
+                }
+                Err(err) => println!("Failed to create the project: {err}"),
+            }
+        }
+
+        if let Some(gallery_dir) = &gallery_dir {
+            match state.gallery_project(&format!("{input} gallery")) {
+                Ok(project) => {
+                    let path = gallery_dir.join(&project.name);
+                    match project.write_to(&path) {
+                        Ok(()) => println!("Wrote the gallery to {}", path.display()),
+                        Err(err) => println!("Failed to write the gallery: {err}"),
+                    }
+                }
+                Err(err) => println!("Failed to create the gallery: {err}"),
+            }
         }
 
         println!("\nTook: {:?}", start_timestamp.elapsed());
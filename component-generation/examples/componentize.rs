@@ -0,0 +1,57 @@
+use component_generation::{DioxusVersion, PartialState};
+
+// Split an existing HTML or JSX page into dioxus components without a model:
+// cargo run --example componentize -- ../output.html [--out <dir>] [--dioxus 0.4]
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut paths = Vec::new();
+    let mut out_dir = None;
+    let mut target = DioxusVersion::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out_dir = args.next().map(std::path::PathBuf::from),
+            "--dioxus" => {
+                target = match args.next().as_deref() {
+                    Some("0.4") => DioxusVersion::V0_4,
+                    _ => DioxusVersion::V0_5,
+                }
+            }
+            _ => paths.push(arg),
+        }
+    }
+    let [path] = paths.as_slice() else {
+        eprintln!("Usage: componentize <page.html> [--out <dir>] [--dioxus 0.4]");
+        std::process::exit(1);
+    };
+
+    let source = std::fs::read_to_string(path).unwrap();
+    let mut state = PartialState::componentize(&source);
+    state.set_target(target);
+
+    match state.app_component() {
+        Ok(app) => println!("{app}\n"),
+        Err(err) => println!("Failed to convert the app: {err}"),
+    }
+    for component in state.components() {
+        match component.component_string_with(state.components()) {
+            Ok(code) => println!("{code}\n"),
+            Err(err) => println!("Failed to convert {}: {err}", component.name),
+        }
+    }
+
+    if let Some(out_dir) = out_dir {
+        let name = std::path::Path::new(path)
+            .file_stem()
+            .map_or_else(|| "app".into(), |stem| stem.to_string_lossy());
+        match state.project(&name) {
+            Ok(project) => {
+                let path = out_dir.join(&project.name);
+                match project.write_to(&path) {
+                    Ok(()) => println!("Wrote the project to {}", path.display()),
+                    Err(err) => println!("Failed to write the project: {err}"),
+                }
+            }
+            Err(err) => println!("Failed to create the project: {err}"),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use component_generation::{DioxusVersion, KalosmBackend, UiGenerator};
+
+// Generate a site with several pages connected by dioxus-router routes:
+// cargo run --example site -- "a website for a small bakery" [--out <dir>] [--dioxus 0.4]
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut descriptions = Vec::new();
+    let mut out_dir = None;
+    let mut target = DioxusVersion::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out_dir = args.next().map(std::path::PathBuf::from),
+            "--dioxus" => {
+                target = match args.next().as_deref() {
+                    Some("0.4") => DioxusVersion::V0_4,
+                    _ => DioxusVersion::V0_5,
+                }
+            }
+            _ => descriptions.push(arg),
+        }
+    }
+    let [description] = descriptions.as_slice() else {
+        eprintln!("Usage: site <description> [--out <dir>] [--dioxus 0.4]");
+        std::process::exit(1);
+    };
+
+    let site = UiGenerator::new(KalosmBackend::default_model().await.unwrap())
+        .with_repair_attempts(2)
+        .with_target(target)
+        .generate_site(description)
+        .await
+        .unwrap();
+
+    for page in site.pages() {
+        println!("{} {}: {}", page.path, page.name, page.description);
+    }
+    println!("\n{}\n", site.routes());
+    if let Some(layout) = site.layout() {
+        println!("{layout}\n");
+    }
+    let components = site
+        .pages()
+        .iter()
+        .map(|page| site.page_component(page))
+        .chain(site.components())
+        .collect::<Vec<_>>();
+    for component in &components {
+        match component.component_string_with(&components) {
+            Ok(code) => println!("{code}\n"),
+            Err(err) => println!("Failed to convert {}: {err}", component.name),
+        }
+    }
+
+    if let Some(out_dir) = out_dir {
+        match site.project(description) {
+            Ok(project) => {
+                let path = out_dir.join(&project.name);
+                match project.write_to(&path) {
+                    Ok(()) => println!("Wrote the project to {}", path.display()),
+                    Err(err) => println!("Failed to write the project: {err}"),
+                }
+            }
+            Err(err) => println!("Failed to create the project: {err}"),
+        }
+    }
+}
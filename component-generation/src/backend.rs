@@ -0,0 +1,231 @@
+use crate::{ConfigError, GenerationConfig, GenerationError, GrammarState, OutputGrammar};
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
+use kalosm::language::*;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// A language model that can stream a response to a prompt.
+pub trait ModelBackend {
+    /// Stream the response to the prompt. The full response must follow the grammar.
+    fn stream_structured_text(
+        &self,
+        prompt: &str,
+        grammar: &OutputGrammar,
+    ) -> BoxStream<'static, String>;
+}
+
+/// A backend that runs a Llama model locally with kalosm.
+#[derive(Clone)]
+pub struct KalosmBackend {
+    model: Llama,
+    config: GenerationConfig,
+    // The number of requests made so far. Seeded sampling uses a different seed for each request
+    requests: Arc<AtomicU64>,
+}
+
+impl KalosmBackend {
+    /// Create a backend from a model that is already loaded.
+    pub fn new(model: Llama) -> Self {
+        Self {
+            model,
+            config: GenerationConfig::default(),
+            requests: Arc::default(),
+        }
+    }
+
+    /// Load the model from the config and sample with the parameters in the config.
+    pub async fn from_config(config: GenerationConfig) -> Result<Self, ConfigError> {
+        let model = Llama::builder()
+            .with_source(config.source())
+            .build()
+            .await
+            .map_err(|err| ConfigError::Model(err.to_string()))?;
+        Ok(Self::new(model).with_config(config))
+    }
+
+    /// Sample with the parameters in the config. The model files in the config are ignored.
+    pub fn with_config(mut self, config: GenerationConfig) -> Self {
+        self.config = config;
+        self.requests = Arc::default();
+        self
+    }
+
+    /// Load a model from a source. Use this to run your own fine-tuned GGUF.
+    pub async fn from_source(source: LlamaSource) -> Result<Self, GenerationError> {
+        let model = Llama::builder()
+            .with_source(source)
+            .build()
+            .await
+            .map_err(|err| GenerationError::ModelLoad {
+                message: err.to_string(),
+            })?;
+        Ok(Self::new(model))
+    }
+
+    /// The fine-tuned model this crate was trained for. The model is only loaded once and shared between calls. If loading fails, the next call tries again.
+    pub async fn default_model() -> Result<Self, GenerationError> {
+        static MODEL: OnceCell<KalosmBackend> = OnceCell::const_new();
+        MODEL
+            .get_or_try_init(|| Self::from_source(GenerationConfig::default().source()))
+            .await
+            .cloned()
+    }
+}
+
+impl From<Llama> for KalosmBackend {
+    fn from(model: Llama) -> Self {
+        Self::new(model)
+    }
+}
+
+impl ModelBackend for KalosmBackend {
+    fn stream_structured_text(
+        &self,
+        prompt: &str,
+        grammar: &OutputGrammar,
+    ) -> BoxStream<'static, String> {
+        let constraints = grammar.clone();
+        let request = self.requests.fetch_add(1, Ordering::SeqCst);
+        let stream: BoxStream<'static, String> = match self.config.parameters(request) {
+            Some(parameters) => {
+                let sampler: Arc<Mutex<dyn Sampler>> = Arc::new(Mutex::new(parameters.sampler()));
+                Box::pin(self.model.stream_structured_text_with_sampler(
+                    prompt,
+                    constraints,
+                    sampler,
+                ))
+            }
+            None => Box::pin(self.model.stream_structured_text(prompt, constraints)),
+        };
+        match self.config.max_chunks {
+            Some(max_chunks) => Box::pin(stream.take(max_chunks)),
+            None => stream,
+        }
+    }
+}
+
+// Let kalosm sample only the tokens the grammar accepts
+impl Parser for OutputGrammar {
+    type Output = ();
+    type PartialState = GrammarState;
+
+    fn parse<'a>(
+        &self,
+        state: &GrammarState,
+        input: &'a [u8],
+    ) -> ParseResult<ParseStatus<'a, GrammarState, ()>> {
+        let mut state = state.clone();
+        for (index, byte) in input.iter().enumerate() {
+            state.push_byte(*byte)?;
+            if state.is_finished() {
+                return Ok(ParseStatus::Finished {
+                    result: (),
+                    remaining: &input[index + 1..],
+                });
+            }
+        }
+        let required_next = state.required_next().into();
+        Ok(ParseStatus::Incomplete {
+            new_state: state,
+            required_next,
+        })
+    }
+}
+
+impl CreateParserState for OutputGrammar {
+    fn create_parser_state(&self) -> GrammarState {
+        self.state()
+    }
+}
+
+/// A deterministic backend that replays canned token streams instead of running a model.
+///
+/// Each call to [`ModelBackend::stream_structured_text`] replays the next scripted response in order. Once every response has been replayed, the backend streams nothing.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedBackend {
+    responses: Arc<Mutex<VecDeque<Vec<String>>>>,
+    prompts: Arc<Mutex<Vec<String>>>,
+}
+
+impl ScriptedBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a response that is streamed as exactly these tokens.
+    pub fn with_tokens(self, tokens: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back(tokens.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a response that is streamed in small chunks of text, like a model would produce it.
+    pub fn with_response(self, response: &str) -> Self {
+        self.with_tokens(split_tokens(response))
+    }
+
+    /// Every prompt the backend was asked to respond to so far, in order. Clones of the backend share the prompts.
+    pub fn prompts(&self) -> Vec<String> {
+        self.prompts.lock().unwrap().clone()
+    }
+}
+
+impl ModelBackend for ScriptedBackend {
+    fn stream_structured_text(
+        &self,
+        prompt: &str,
+        _: &OutputGrammar,
+    ) -> BoxStream<'static, String> {
+        self.prompts.lock().unwrap().push(prompt.to_string());
+        let tokens = self
+            .responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_default();
+        Box::pin(futures_util::stream::iter(tokens))
+    }
+}
+
+// Split text into word sized chunks. Each chunk keeps the whitespace that follows it so the chunks join back into the original text.
+fn split_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        if in_whitespace && !c.is_whitespace() {
+            tokens.push(std::mem::take(&mut current));
+        }
+        in_whitespace = c.is_whitespace();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[tokio::test]
+async fn truncated_response_is_an_error() {
+    use crate::{GenerationError, UiGenerator};
+
+    // The response stops in the middle of the HTML like it would at the token limit
+    let backend = ScriptedBackend::new().with_response(
+        "A footer.
+components:
+- Footer: The page footer
+HTML:
+<div><Footer/>",
+    );
+    let error = UiGenerator::new(backend)
+        .generate("a footer")
+        .await
+        .unwrap_err();
+    assert!(matches!(&error, GenerationError::Truncated { .. }));
+    assert!(error.raw_output().unwrap().ends_with("<div><Footer/>"));
+}
@@ -0,0 +1,285 @@
+use crate::{BatchError, ModelBackend, PartialState, UiGenerator};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// A line in a batch prompt file. Lines are either a JSON string with the prompt or an object with the prompt and an optional id.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum BatchPrompt {
+    Text(String),
+    Object {
+        #[serde(default)]
+        id: Option<String>,
+        prompt: String,
+    },
+}
+
+impl BatchPrompt {
+    // Results are matched to prompts by id, or by the prompt itself if there is no id
+    fn into_parts(self) -> (String, String) {
+        match self {
+            BatchPrompt::Text(prompt) => (prompt.clone(), prompt),
+            BatchPrompt::Object { id, prompt } => (id.unwrap_or_else(|| prompt.clone()), prompt),
+        }
+    }
+}
+
+/// The result of generating a UI for one prompt in a batch. Each result is written as one line of JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub id: String,
+    pub prompt: String,
+    pub description: String,
+    pub html: String,
+    /// The code of the app component, or `None` if it could not be converted
+    pub app: Option<String>,
+    pub app_diagnostics: Vec<String>,
+    pub components: Vec<BatchComponent>,
+    /// The error that stopped the generation, if any
+    pub error: Option<String>,
+    /// How long the generation took in milliseconds
+    pub generation_ms: u64,
+    /// How long converting the HTML to code took in milliseconds
+    pub conversion_ms: u64,
+}
+
+/// A component generated for one prompt in a batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchComponent {
+    pub name: String,
+    pub description: String,
+    pub html: String,
+    /// The code of the component, or `None` if it could not be converted
+    pub code: Option<String>,
+    /// The error from converting the component, if any
+    pub error: Option<String>,
+    pub diagnostics: Vec<String>,
+}
+
+/// How many prompts a batch generated, skipped and failed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub generated: usize,
+    /// Prompts that already had a result from an earlier run, or that have the same id as an earlier prompt
+    pub skipped: usize,
+    /// Prompts that failed to generate. Their errors are written to the results.
+    pub failed: usize,
+}
+
+impl BatchResult {
+    fn new(id: String, prompt: String) -> Self {
+        Self {
+            id,
+            prompt,
+            description: String::new(),
+            html: String::new(),
+            app: None,
+            app_diagnostics: Vec::new(),
+            components: Vec::new(),
+            error: None,
+            generation_ms: 0,
+            conversion_ms: 0,
+        }
+    }
+
+    fn with_state(mut self, state: &PartialState) -> Self {
+        let start = Instant::now();
+        self.description = state.description.clone();
+        self.html = state.html.clone();
+        match state.app_component() {
+            Ok(app) => self.app = Some(app),
+            Err(err) => self.error = Some(err.to_string()),
+        }
+        self.app_diagnostics = state
+            .app_diagnostics()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        self.components = state
+            .components()
+            .iter()
+            .map(|component| {
+                let (code, error) = match component.component_string_with(state.components()) {
+                    Ok(code) => (Some(code), None),
+                    Err(err) => (None, Some(err.to_string())),
+                };
+                BatchComponent {
+                    name: component.name.clone(),
+                    description: component.description.clone(),
+                    html: component.html.clone(),
+                    code,
+                    error,
+                    diagnostics: component
+                        .diagnostics()
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                }
+            })
+            .collect();
+        self.conversion_ms = start.elapsed().as_millis() as u64;
+        self
+    }
+}
+
+impl<B: ModelBackend> UiGenerator<B> {
+    /// Generate a UI for every prompt in a JSONL file and append one [`BatchResult`] per line to the results file.
+    ///
+    /// Each result is written as soon as it is finished. Prompts that already have a result without an error in the results file are skipped, so a batch that was stopped can be restarted with the same files. Prompts that failed are generated again, and the new result is appended after the failed one. Prompts with the same id as an earlier prompt are skipped.
+    pub async fn generate_batch(
+        &self,
+        prompts: impl AsRef<Path>,
+        results: impl AsRef<Path>,
+    ) -> Result<BatchSummary, BatchError> {
+        let prompts = read_prompts(prompts.as_ref())?;
+        let results = results.as_ref();
+        let mut finished = finished_ids(results)?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(results)?;
+        // A batch that was killed while writing can leave a partial line behind
+        if !ends_with_newline(results)? {
+            writeln!(file)?;
+        }
+
+        let mut summary = BatchSummary::default();
+        for (id, prompt) in prompts {
+            if !finished.insert(id.clone()) {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let start = Instant::now();
+            let generated = self.generate(&prompt).await;
+            let generation_ms = start.elapsed().as_millis() as u64;
+            let mut result = BatchResult::new(id, prompt);
+            match generated {
+                Ok(state) => {
+                    result = result.with_state(&state);
+                    summary.generated += 1;
+                }
+                Err(err) => {
+                    result.error = Some(err.to_string());
+                    summary.failed += 1;
+                }
+            }
+            result.generation_ms = generation_ms;
+
+            let line = serde_json::to_string(&result)?;
+            writeln!(file, "{line}")?;
+            file.flush()?;
+        }
+
+        Ok(summary)
+    }
+}
+
+fn read_prompts(path: &Path) -> Result<Vec<(String, String)>, BatchError> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let mut prompts = Vec::new();
+    for (index, line) in file.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let prompt = serde_json::from_str::<BatchPrompt>(&line).map_err(|source| {
+            BatchError::InvalidPrompt {
+                line: index + 1,
+                source,
+            }
+        })?;
+        prompts.push(prompt.into_parts());
+    }
+    Ok(prompts)
+}
+
+// The ids of the prompts that already have a result without an error. Lines that are not valid results are ignored
+fn finished_ids(path: &Path) -> Result<HashSet<String>, BatchError> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(HashSet::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut ids = HashSet::new();
+    for line in std::io::BufReader::new(file).lines() {
+        match serde_json::from_str::<BatchResult>(&line?) {
+            Ok(result) if result.error.is_none() => {
+                ids.insert(result.id);
+            }
+            _ => {}
+        }
+    }
+    Ok(ids)
+}
+
+fn ends_with_newline(path: &Path) -> std::io::Result<bool> {
+    let contents = std::fs::read(path)?;
+    Ok(contents.last().is_none_or(|byte| *byte == b'\n'))
+}
+
+#[tokio::test]
+async fn resume_batch() {
+    use crate::ScriptedBackend;
+
+    let dir =
+        std::env::temp_dir().join(format!("component-generation-batch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let prompts = dir.join("prompts.jsonl");
+    let results = dir.join("results.jsonl");
+    std::fs::write(
+        &prompts,
+        "\"a login page\"\n{\"id\": \"pricing\", \"prompt\": \"a pricing table\"}\n{\"id\": \"pricing\", \"prompt\": \"a pricing page\"}\n",
+    )
+    .unwrap();
+    let mut earlier = BatchResult::new("a login page".to_string(), "a login page".to_string());
+    earlier.description = "A login page.".to_string();
+    let mut failed = BatchResult::new("pricing".to_string(), "a pricing table".to_string());
+    failed.error = Some("the model stopped early".to_string());
+    // The earlier run failed the second prompt and was killed while writing its next result
+    std::fs::write(
+        &results,
+        format!(
+            "{}\n{}\n{{\"id\": \"pri",
+            serde_json::to_string(&earlier).unwrap(),
+            serde_json::to_string(&failed).unwrap()
+        ),
+    )
+    .unwrap();
+    assert!(!finished_ids(&results).unwrap().contains("pricing"));
+
+    let backend = ScriptedBackend::new().with_response(
+        "A pricing table.
+components:
+- Plan: A pricing plan
+HTML:
+<div><Plan/></div>
+component html:
+Plan:
+<div class=\"rounded\">{price}</div><|eot_id|>",
+    );
+    let summary = UiGenerator::new(backend)
+        .generate_batch(&prompts, &results)
+        .await
+        .unwrap();
+    assert_eq!(
+        summary,
+        BatchSummary {
+            generated: 1,
+            skipped: 2,
+            failed: 0
+        }
+    );
+
+    let ids = finished_ids(&results).unwrap();
+    assert!(ids.contains("a login page") && ids.contains("pricing"));
+    let written = std::fs::read_to_string(&results).unwrap();
+    let last = serde_json::from_str::<BatchResult>(written.lines().last().unwrap()).unwrap();
+    assert_eq!(last.description, "A pricing table.");
+    assert_eq!(last.components[0].name, "Plan");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
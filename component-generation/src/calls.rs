@@ -0,0 +1,305 @@
+use crate::interactive::{block_end, string_mask};
+use crate::naming::prop_name;
+use crate::preview::typed_sample_value;
+use crate::{Prop, PropType};
+use regex::{Captures, Regex};
+use std::ops::Range;
+
+// Component tags are swapped for this prefix and a lowercase index while rosetta converts the HTML
+const MARKER_PREFIX: &str = "componentcall";
+
+/// Swap the tags of components for lowercase markers. Rosetta writes every tag in snake case, so `<Footer/>` would turn into the `footer` element. Returns the HTML and the name of the component behind each marker.
+pub(crate) fn mark_components(html: &str) -> (String, Vec<String>) {
+    let tag_regex = Regex::new(r"<(/?)([A-Z][A-Za-z0-9_]*)").unwrap();
+    let mut names: Vec<String> = Vec::new();
+    let html = tag_regex
+        .replace_all(html, |tag: &Captures| {
+            let index = match names.iter().position(|name| *name == tag[2]) {
+                Some(index) => index,
+                None => {
+                    names.push(tag[2].to_string());
+                    names.len() - 1
+                }
+            };
+            format!("<{}{}", &tag[1], marker(index))
+        })
+        .to_string();
+    (html, names)
+}
+
+/// Swap the markers in the rsx back for the components and rename the attributes of each call to the props they set. `componentcalla { "ctaUrl": "/" }` becomes `Hero { cta_url: "/" }`
+pub(crate) fn unmark_components(rsx: &str, names: &[String]) -> String {
+    if names.is_empty() {
+        return rsx.to_string();
+    }
+    let marker_regex = Regex::new(&format!(r"\b{MARKER_PREFIX}[a-z]+\b")).unwrap();
+    let strings = string_mask(rsx);
+    let mut edits = Vec::new();
+    for found in marker_regex.find_iter(rsx) {
+        if strings[found.start()] {
+            continue;
+        }
+        let Some(name) = (0..names.len())
+            .find(|index| marker(*index) == found.as_str())
+            .map(|index| &names[index])
+        else {
+            continue;
+        };
+        edits.push((found.range(), name.clone()));
+        let Some(open) = block_start(rsx, found.end()) else {
+            continue;
+        };
+        for attribute in call_attributes(rsx, open) {
+            let prop = prop_name(
+                rsx[attribute.name.clone()]
+                    .trim_start_matches("r#")
+                    .trim_matches('"'),
+            );
+            edits.push((attribute.name, prop));
+        }
+    }
+    apply_edits(rsx, edits)
+}
+
+/// Pass every required prop of the components to their calls in the `rsx!` blocks of the code. Missing props get a sample value, and literal strings passed to number or bool props are unquoted.
+pub(crate) fn pass_props(code: &str, components: &[(String, Vec<Prop>)]) -> String {
+    if components.is_empty() {
+        return code.to_string();
+    }
+    let rsx_regex = Regex::new(r"\brsx!\s*\{").unwrap();
+    let call_regex = Regex::new(r"\b([A-Z][A-Za-z0-9_]*)\s*\{").unwrap();
+    let strings = string_mask(code);
+    let mut edits = Vec::new();
+    let mut searched = 0;
+    for rsx in rsx_regex.find_iter(code) {
+        if rsx.start() < searched || strings[rsx.start()] {
+            continue;
+        }
+        let end = block_end(code, rsx.end() - 1);
+        searched = end;
+        for call in call_regex.captures_iter(&code[rsx.end()..end]) {
+            let whole = call.get(0).unwrap();
+            let start = rsx.end() + whole.start();
+            if strings[start] {
+                continue;
+            }
+            let Some((_, props)) = components.iter().find(|(name, _)| *name == call[1]) else {
+                continue;
+            };
+            let open = rsx.end() + whole.end() - 1;
+            edits.extend(call_edits(code, open, props));
+        }
+    }
+    apply_edits(code, edits)
+}
+
+// The edits that type the literal props and add the missing props of the call with the body opened at `open`
+fn call_edits(code: &str, open: usize, props: &[Prop]) -> Vec<(Range<usize>, String)> {
+    let attributes = call_attributes(code, open);
+    let mut edits = Vec::new();
+    let mut missing = Vec::new();
+    for prop in props {
+        let passed = attributes
+            .iter()
+            .find(|attribute| code[attribute.name.clone()] == prop.name);
+        match passed {
+            Some(attribute) => {
+                let value = &code[attribute.value.clone()];
+                let Some(literal) = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                else {
+                    continue;
+                };
+                if let Some(typed) = typed_literal(literal, prop.ty) {
+                    edits.push((attribute.value.clone(), typed));
+                }
+            }
+            // Children are passed as the body of the call
+            None if prop.optional || prop.ty == PropType::Element => {}
+            None => {
+                let sample = typed_sample_value(&prop.name, prop.ty, None);
+                let value = match prop.ty {
+                    PropType::String => format!("{sample:?}"),
+                    _ => match typed_literal(&sample, prop.ty) {
+                        Some(value) => value,
+                        None => continue,
+                    },
+                };
+                missing.push(format!("{}: {value}", prop.name));
+            }
+        }
+    }
+    if !missing.is_empty() {
+        let close = block_end(code, open);
+        let missing = missing.join(", ");
+        match code[open + 1..close].trim().is_empty() {
+            true => edits.push((open..close + 1, format!("{{ {missing} }}"))),
+            false => edits.push((open + 1..open + 1, format!(" {missing},"))),
+        }
+    }
+    edits
+}
+
+// The literal as the rust value of a number or bool prop, or `None` if the prop is a string or the literal is not a value of the type
+fn typed_literal(literal: &str, ty: PropType) -> Option<String> {
+    match ty {
+        PropType::Integer => literal.parse::<i64>().ok().map(|value| value.to_string()),
+        PropType::Float => {
+            literal.parse::<f64>().ok()?;
+            match literal.contains('.') {
+                true => Some(literal.to_string()),
+                false => Some(format!("{literal}.0")),
+            }
+        }
+        PropType::Bool => literal.parse::<bool>().ok().map(|value| value.to_string()),
+        _ => None,
+    }
+}
+
+// An attribute of a call. The name is quoted if it is not a valid identifier
+struct CallAttribute {
+    name: Range<usize>,
+    value: Range<usize>,
+}
+
+// The attributes directly inside the body of the call opened at `open`. Attributes start the body or follow a comma.
+fn call_attributes(rsx: &str, open: usize) -> Vec<CallAttribute> {
+    let attribute_regex = Regex::new(r#"^("[^"\\]*"|(?:r#)?[A-Za-z_][\w-]*)\s*:([^:]|$)"#).unwrap();
+    let strings = string_mask(rsx);
+    let close = block_end(rsx, open);
+    let mut attributes = Vec::new();
+    let mut depth = 0;
+    let mut candidates = vec![open + 1];
+    let body = rsx.bytes().enumerate().take(close).skip(open + 1);
+    for (index, byte) in body {
+        if strings[index] {
+            continue;
+        }
+        match byte {
+            b'{' => depth += 1,
+            b'}' => depth -= 1,
+            b',' if depth == 0 => candidates.push(index + 1),
+            _ => {}
+        }
+    }
+    for candidate in candidates {
+        let rest = &rsx[candidate..close];
+        let start = candidate + rest.len() - rest.trim_start().len();
+        let Some(attribute) = attribute_regex.captures(&rsx[start..close]) else {
+            continue;
+        };
+        let name = attribute.get(1).unwrap();
+        let value_start = start + attribute.get(2).unwrap().start();
+        let value_start =
+            value_start + rsx[value_start..].len() - rsx[value_start..].trim_start().len();
+        let value_end = match rsx.as_bytes().get(value_start) {
+            Some(b'"') => (value_start + 1..close)
+                .find(|index| !strings[*index])
+                .unwrap_or(close),
+            _ => (value_start..close)
+                .find(|index| rsx.as_bytes()[*index] == b',' && !strings[*index])
+                .unwrap_or(close),
+        };
+        attributes.push(CallAttribute {
+            name: start + name.start()..start + name.end(),
+            value: value_start..value_end,
+        });
+    }
+    attributes
+}
+
+// The index of the brace that opens the body of a call, if only whitespace comes before it
+fn block_start(rsx: &str, after_name: usize) -> Option<usize> {
+    let rest = &rsx[after_name..];
+    let open = after_name + rest.len() - rest.trim_start().len();
+    rsx[open..].starts_with('{').then_some(open)
+}
+
+fn apply_edits(text: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(range, _)| range.start);
+    let mut output = String::new();
+    let mut copied = 0;
+    for (range, replacement) in edits {
+        if range.start < copied {
+            continue;
+        }
+        output += &text[copied..range.start];
+        output += &replacement;
+        copied = range.end;
+    }
+    output += &text[copied..];
+    output
+}
+
+// The marker for the nth component, like `componentcalla`. The index is written in lowercase letters because rosetta would split digits from the rest of the tag
+fn marker(index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut index = index;
+    loop {
+        letters.push(b'a' + (index % 26) as u8);
+        index /= 26;
+        if index == 0 {
+            break;
+        }
+    }
+    letters.reverse();
+    format!("{MARKER_PREFIX}{}", String::from_utf8(letters).unwrap())
+}
+
+#[test]
+fn component_calls() {
+    let (html, names) =
+        mark_components(r#"<div><Hero ctaUrl="/start"/><footer>x</footer><Footer></Footer></div>"#);
+    assert_eq!(
+        html,
+        r#"<div><componentcalla ctaUrl="/start"/><footer>x</footer><componentcallb></componentcallb></div>"#
+    );
+    assert_eq!(names, ["Hero", "Footer"]);
+
+    let rsx = r#"div {
+    componentcalla { "ctaUrl": "/start", r#type: "wide", "count": "3", "{children}" }
+    footer { "x" }
+    componentcallb {}
+}"#;
+    let rsx = unmark_components(rsx, &names);
+    assert_eq!(
+        rsx,
+        r#"div {
+    Hero { cta_url: "/start", type_value: "wide", count: "3", "{children}" }
+    footer { "x" }
+    Footer {}
+}"#
+    );
+
+    let prop = |name: &str, ty, optional| Prop {
+        name: name.to_string(),
+        ty,
+        optional,
+    };
+    let components = [
+        (
+            "Hero".to_string(),
+            vec![
+                prop("title", PropType::String, false),
+                prop("cta_url", PropType::String, false),
+                prop("count", PropType::Integer, false),
+                prop("children", PropType::Element, false),
+            ],
+        ),
+        (
+            "Footer".to_string(),
+            vec![
+                prop("copyright", PropType::String, false),
+                prop("rating", PropType::Float, false),
+                prop("show_links", PropType::Bool, true),
+            ],
+        ),
+    ];
+    let code = pass_props(&format!("rsx! {{ {rsx} }}"), &components);
+    assert!(code.contains(
+        r#"Hero { title: "Title", cta_url: "/start", type_value: "wide", count: 3, "{children}" }"#
+    ));
+    assert!(code.contains(r#"Footer { copyright: "Copyright", rating: 4.5 }"#));
+    assert!(code.contains(r#"footer { "x" }"#));
+}
@@ -0,0 +1,99 @@
+/// The chat format a model was fine-tuned with. The template formats the prompt, and its stop token ends the response of the model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ChatTemplate {
+    /// The Llama 3 format with `<|start_header_id|>` headers. The default model uses this format.
+    #[default]
+    Llama3,
+    /// The ChatML format with `<|im_start|>` and `<|im_end|>`, used by Qwen and many other fine-tunes
+    ChatMl,
+    /// The Phi-3 format with `<|user|>` and `<|assistant|>` tags
+    Phi3,
+    /// The Mistral instruct format with `[INST]` tags. It has no system role, so the system prompt is put before the user prompt.
+    Mistral,
+}
+
+impl ChatTemplate {
+    /// Format a prompt with an optional system prompt. The response of the assistant starts with `response_start`
+    pub fn prompt(self, system: Option<&str>, prompt: &str, response_start: &str) -> String {
+        let mut formatted = String::new();
+        match self {
+            ChatTemplate::Llama3 => {
+                if let Some(system) = system {
+                    formatted +=
+                        &format!("<|start_header_id|>system<|end_header_id|>{system}<|eot_id|>");
+                }
+                formatted += &format!(
+                    "<|start_header_id|>user<|end_header_id|>{prompt}<|eot_id|><|start_header_id|>assistant<|end_header_id|>"
+                );
+            }
+            ChatTemplate::ChatMl => {
+                if let Some(system) = system {
+                    formatted += &format!("<|im_start|>system\n{system}<|im_end|>\n");
+                }
+                formatted +=
+                    &format!("<|im_start|>user\n{prompt}<|im_end|>\n<|im_start|>assistant");
+            }
+            ChatTemplate::Phi3 => {
+                if let Some(system) = system {
+                    formatted += &format!("<|system|>\n{system}<|end|>\n");
+                }
+                formatted += &format!("<|user|>\n{prompt}<|end|>\n<|assistant|>");
+            }
+            ChatTemplate::Mistral => {
+                formatted += "[INST] ";
+                if let Some(system) = system {
+                    formatted += &format!("{system}\n\n");
+                }
+                formatted += &format!("{prompt} [/INST]");
+            }
+        }
+        formatted + response_start
+    }
+
+    /// The token the model writes at the end of its response. Every stop token starts with `<`, so the grammar only accepts it after the top level HTML is finished. It can still look like HTML: Mistral's `</s>` also closes an `<s>` element.
+    pub fn stop_token(self) -> &'static str {
+        match self {
+            ChatTemplate::Llama3 => "<|eot_id|>",
+            ChatTemplate::ChatMl => "<|im_end|>",
+            ChatTemplate::Phi3 => "<|end|>",
+            ChatTemplate::Mistral => "</s>",
+        }
+    }
+
+    /// The text without the stop token at its end, or all of the text if it doesn't end with the stop token. Only pass the end of the response, because the stop token can also be part of the HTML.
+    pub fn strip_stop_token(self, text: &str) -> &str {
+        text.strip_suffix(self.stop_token()).unwrap_or(text)
+    }
+}
+
+#[test]
+fn chat_templates() {
+    assert_eq!(
+        ChatTemplate::Llama3.prompt(None, "a footer", "\nDESCRIPTION:\n"),
+        "<|start_header_id|>user<|end_header_id|>a footer<|eot_id|><|start_header_id|>assistant<|end_header_id|>\nDESCRIPTION:\n"
+    );
+    assert_eq!(
+        ChatTemplate::ChatMl.prompt(Some("Write HTML."), "a footer", "\n"),
+        "<|im_start|>system\nWrite HTML.<|im_end|>\n<|im_start|>user\na footer<|im_end|>\n<|im_start|>assistant\n"
+    );
+    assert_eq!(
+        ChatTemplate::Phi3.prompt(None, "a footer", "\n"),
+        "<|user|>\na footer<|end|>\n<|assistant|>\n"
+    );
+    assert_eq!(
+        ChatTemplate::Mistral.prompt(Some("Write HTML."), "a footer", "\n"),
+        "[INST] Write HTML.\n\na footer [/INST]\n"
+    );
+    assert_eq!(
+        ChatTemplate::Mistral.strip_stop_token("<footer></footer></s>"),
+        "<footer></footer>"
+    );
+    assert_eq!(
+        ChatTemplate::Llama3.strip_stop_token("<footer></footer>"),
+        "<footer></footer>"
+    );
+    assert_eq!(
+        ChatTemplate::Mistral.strip_stop_token("<p><s>$99</s> $49</p></s>"),
+        "<p><s>$99</s> $49</p>"
+    );
+}
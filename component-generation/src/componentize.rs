@@ -0,0 +1,814 @@
+use crate::naming::{component_name, prop_name};
+use crate::props::{BOOLEAN_ATTRIBUTES, NUMERIC_ATTRIBUTES};
+use crate::{Component, DioxusVersion, PartialState};
+use convert_case::{Case, Casing};
+use regex::Regex;
+use std::collections::HashMap;
+
+// Elements that never have children
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+// Elements that are dropped from the page because they do not render anything
+const DROPPED_TAGS: &[&str] = &["head", "script", "style", "noscript", "template"];
+
+// Repeated subtrees with fewer elements than this stay inline
+const MIN_COMPONENT_ELEMENTS: usize = 2;
+
+// SVG attributes that keep their camel case name in HTML
+const CAMEL_CASE_ATTRIBUTES: &[&str] = &[
+    "viewBox",
+    "preserveAspectRatio",
+    "gradientUnits",
+    "gradientTransform",
+    "patternUnits",
+    "patternTransform",
+];
+
+// JSX attributes for SVG presentation that are written in kebab case in HTML. Other camel case attributes like `tabIndex` are lowercased
+const KEBAB_CASE_PREFIXES: &[&str] = &["stroke", "fill", "clip", "font", "stop", "text", "marker"];
+
+// CSS properties that take numbers without a unit in JSX style objects
+const UNITLESS_STYLES: &[&str] = &[
+    "opacity",
+    "z-index",
+    "font-weight",
+    "flex",
+    "flex-grow",
+    "flex-shrink",
+    "line-height",
+    "order",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Element {
+        tag: String,
+        attributes: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+    // Comments are kept because they are the placeholders for svg icons
+    Comment(String),
+    // A repeated subtree that was replaced by a component
+    Instance {
+        group: usize,
+        index: usize,
+    },
+}
+
+// Subtrees with the same shape that become one component
+struct Group {
+    shape: String,
+    name: String,
+    instances: Vec<Node>,
+    template: Option<Node>,
+    // The props passed to each instance
+    values: Vec<Vec<(String, String)>>,
+}
+
+impl PartialState {
+    /// Split an HTML or JSX page into an app and components without running a model.
+    ///
+    /// Subtrees that appear more than once with the same structure become a component. Text and attribute values that differ between the copies become props, and each copy is replaced with the component in the app. The state is validated, so it can be used like a generated UI to write a project or preview.
+    pub fn componentize(source: &str) -> Self {
+        let nodes = page_body(parse_html(&jsx_to_html(source)));
+
+        let mut counts = HashMap::new();
+        for node in &nodes {
+            count_shapes(node, &mut counts);
+        }
+        let mut extractor = Extractor {
+            counts,
+            groups: Vec::new(),
+        };
+        let nodes = extractor.extract(nodes);
+        let groups = extractor.finish();
+
+        let mut state = PartialState::new(String::new(), DioxusVersion::default());
+        state.description = page_title(source).unwrap_or_default();
+        state.html = write_html(&nodes, &groups);
+        for group in &groups {
+            let template = group
+                .template
+                .as_ref()
+                .map_or_else(String::new, |template| {
+                    write_html(std::slice::from_ref(template), &groups)
+                });
+            state.components.push(Component {
+                name: group.name.clone(),
+                description: format!("Extracted from {} similar elements", group.instances.len()),
+                html: template,
+                state: Vec::new(),
+                diagnostics: Vec::new(),
+                target: state.target,
+                links: Vec::new(),
+            });
+        }
+        state.sanitize_names();
+        state.validate();
+        state
+    }
+}
+
+struct Extractor {
+    counts: HashMap<String, usize>,
+    groups: Vec<Group>,
+}
+
+impl Extractor {
+    // Replace every repeated subtree with an instance of its group. Repeated subtrees are not searched for smaller repeated subtrees until their template is built
+    fn extract(&mut self, nodes: Vec<Node>) -> Vec<Node> {
+        nodes
+            .into_iter()
+            .map(|node| {
+                if !matches!(node, Node::Element { .. }) {
+                    return node;
+                }
+                let shape = shape(&node);
+                let repeated = self.counts.get(&shape).copied().unwrap_or_default() > 1;
+                if repeated && element_count(&node) >= MIN_COMPONENT_ELEMENTS {
+                    return self.add_instance(shape, node);
+                }
+                match node {
+                    Node::Element {
+                        tag,
+                        attributes,
+                        children,
+                    } => Node::Element {
+                        tag,
+                        attributes,
+                        children: self.extract(children),
+                    },
+                    node => node,
+                }
+            })
+            .collect()
+    }
+
+    fn add_instance(&mut self, shape: String, node: Node) -> Node {
+        let group = match self.groups.iter().position(|group| group.shape == shape) {
+            Some(group) => group,
+            None => {
+                let base = match &node {
+                    Node::Element { tag, .. } => component_name(base_name(tag)),
+                    _ => "Component".to_string(),
+                };
+                let mut name = base.clone();
+                let mut suffix = 2;
+                while self.groups.iter().any(|group| group.name == name) {
+                    name = format!("{base}{suffix}");
+                    suffix += 1;
+                }
+                self.groups.push(Group {
+                    shape,
+                    name,
+                    instances: Vec::new(),
+                    template: None,
+                    values: Vec::new(),
+                });
+                self.groups.len() - 1
+            }
+        };
+        let index = self.groups[group].instances.len();
+        self.groups[group].instances.push(node);
+        Node::Instance { group, index }
+    }
+
+    // Build the template and props of every group. Templates can add more groups for the repeated subtrees inside them
+    fn finish(mut self) -> Vec<Group> {
+        let mut index = 0;
+        while index < self.groups.len() {
+            let instances = self.groups[index].instances.iter().collect::<Vec<_>>();
+            let mut props = Vec::new();
+            let template = build_template(&instances, "", &mut props);
+            let values = (0..instances.len())
+                .map(|instance| {
+                    props
+                        .iter()
+                        .map(|(name, values)| (name.clone(), values[instance].clone()))
+                        .collect()
+                })
+                .collect();
+            let template = match template {
+                Node::Element {
+                    tag,
+                    attributes,
+                    children,
+                } => Node::Element {
+                    tag,
+                    attributes,
+                    children: self.extract(children),
+                },
+                template => template,
+            };
+            self.groups[index].template = Some(template);
+            self.groups[index].values = values;
+            index += 1;
+        }
+        self.groups
+    }
+}
+
+// Merge subtrees with the same shape into one template. Values that differ between the subtrees are replaced with placeholders and added to the props with the value for each subtree
+fn build_template(nodes: &[&Node], parent: &str, props: &mut Vec<(String, Vec<String>)>) -> Node {
+    match nodes[0] {
+        Node::Element {
+            tag,
+            attributes,
+            children,
+        } => {
+            let attributes = attributes
+                .iter()
+                .enumerate()
+                .map(|(index, (name, value))| {
+                    let values = nodes
+                        .iter()
+                        .map(|node| match node {
+                            Node::Element { attributes, .. } => attributes[index].1.clone(),
+                            _ => String::new(),
+                        })
+                        .collect::<Vec<_>>();
+                    if values.iter().all(|other| other == value) {
+                        return (name.clone(), value.clone());
+                    }
+                    let prop = add_prop(props, attribute_prop_name(tag, name), values);
+                    (name.clone(), format!("{{{prop}}}"))
+                })
+                .collect();
+            let children = (0..children.len())
+                .map(|index| {
+                    let children = nodes
+                        .iter()
+                        .map(|node| match node {
+                            Node::Element { children, .. } => &children[index],
+                            node => *node,
+                        })
+                        .collect::<Vec<_>>();
+                    build_template(&children, tag, props)
+                })
+                .collect();
+            Node::Element {
+                tag: tag.clone(),
+                attributes,
+                children,
+            }
+        }
+        Node::Text(text) => {
+            let values = nodes
+                .iter()
+                .map(|node| match node {
+                    Node::Text(text) => text.trim().to_string(),
+                    _ => String::new(),
+                })
+                .collect::<Vec<_>>();
+            if values.iter().all(|other| *other == text.trim()) {
+                return Node::Text(text.clone());
+            }
+            let prop = add_prop(props, text_prop_name(parent), values);
+            Node::Text(format!("{{{prop}}}"))
+        }
+        node => node.clone(),
+    }
+}
+
+// Add a prop with a name that is not taken yet and return the name
+fn add_prop(props: &mut Vec<(String, Vec<String>)>, base: &str, values: Vec<String>) -> String {
+    let base = prop_name(base);
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while props.iter().any(|(prop, _)| *prop == name) {
+        name = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    props.push((name.clone(), values));
+    name
+}
+
+pub(crate) fn attribute_prop_name<'a>(tag: &str, attribute: &'a str) -> &'a str {
+    match (tag, attribute) {
+        ("img", "src") => "image_url",
+        ("img", "alt") => "image_alt",
+        (_, "href") => "url",
+        _ => attribute,
+    }
+}
+
+// Text props are named after the element they are in
+pub(crate) fn text_prop_name(parent: &str) -> &'static str {
+    match parent {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "title",
+        "p" | "blockquote" => "description",
+        "a" => "link_text",
+        "button" => "button_text",
+        "span" | "strong" | "em" | "b" | "i" | "small" | "label" => "label",
+        "li" => "item",
+        "td" | "th" => "cell",
+        _ => "text",
+    }
+}
+
+// Components are named after the element at the root of the repeated subtree
+pub(crate) fn base_name(tag: &str) -> &str {
+    match tag {
+        "div" => "Card",
+        "li" => "ListItem",
+        "a" => "LinkCard",
+        "tr" => "TableRow",
+        "img" => "Image",
+        "nav" => "Navigation",
+        _ => tag,
+    }
+}
+
+// The structure of a subtree without the text and attribute values. Subtrees with the same shape become the same component. Boolean and numeric attributes are part of the shape because props for them would not be strings.
+fn shape(node: &Node) -> String {
+    match node {
+        Node::Element {
+            tag,
+            attributes,
+            children,
+        } => {
+            let attributes = attributes
+                .iter()
+                .map(|(name, value)| {
+                    if BOOLEAN_ATTRIBUTES.contains(&name.as_str())
+                        || NUMERIC_ATTRIBUTES.contains(&name.as_str())
+                    {
+                        format!("{name}={value}")
+                    } else {
+                        name.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            let children = children.iter().map(shape).collect::<Vec<_>>().join(",");
+            format!("{tag}[{attributes}]({children})")
+        }
+        Node::Text(_) => "#".to_string(),
+        Node::Comment(comment) => format!("!{comment}"),
+        Node::Instance { group, .. } => format!("@{group}"),
+    }
+}
+
+fn count_shapes(node: &Node, counts: &mut HashMap<String, usize>) {
+    if let Node::Element { children, .. } = node {
+        *counts.entry(shape(node)).or_default() += 1;
+        for child in children {
+            count_shapes(child, counts);
+        }
+    }
+}
+
+fn element_count(node: &Node) -> usize {
+    match node {
+        Node::Element { children, .. } => 1 + children.iter().map(element_count).sum::<usize>(),
+        _ => 0,
+    }
+}
+
+fn write_html(nodes: &[Node], groups: &[Group]) -> String {
+    let mut html = String::new();
+    for node in nodes {
+        match node {
+            Node::Element {
+                tag,
+                attributes,
+                children,
+            } => {
+                html += &format!("<{tag}{}>", write_attributes(attributes));
+                if !VOID_TAGS.contains(&tag.as_str()) {
+                    html += &write_html(children, groups);
+                    html += &format!("</{tag}>");
+                }
+            }
+            Node::Text(text) => html += text,
+            Node::Comment(comment) => html += &format!("<!-- {comment} -->"),
+            Node::Instance { group, index } => {
+                let group = &groups[*group];
+                html += &format!(
+                    "<{}{}/>",
+                    group.name,
+                    write_attributes(&group.values[*index])
+                );
+            }
+        }
+    }
+    html
+}
+
+fn write_attributes(attributes: &[(String, String)]) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| format!(" {name}=\"{}\"", value.replace('"', "&quot;")))
+        .collect()
+}
+
+// Parse HTML into a tree. Unclosed elements are closed at the end of their parent and stray closing tags are ignored
+fn parse_html(html: &str) -> Vec<Node> {
+    let open_tag_regex = Regex::new(
+        r#"^<([A-Za-z][\w:-]*)((?:\s+[^\s=/>"']+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>"']+))?)*)\s*(/?)>"#,
+    )
+    .unwrap();
+    let attribute_regex =
+        Regex::new(r#"([^\s=/>"']+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>"']+)))?"#).unwrap();
+    let close_tag_regex = Regex::new(r"^</\s*([A-Za-z][\w:-]*)\s*>").unwrap();
+
+    // The open elements. The first element holds the top level nodes
+    let mut stack = vec![Node::Element {
+        tag: String::new(),
+        attributes: Vec::new(),
+        children: Vec::new(),
+    }];
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").unwrap_or(comment.len());
+            push_node(&mut stack, Node::Comment(comment[..end].trim().to_string()));
+            rest = comment.get(end + 3..).unwrap_or_default();
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            rest = &rest[end..];
+        } else if let Some(close) = close_tag_regex.captures(rest) {
+            let tag = close[1].to_lowercase();
+            let is_open =
+                |node: &Node| matches!(node, Node::Element { tag: open, .. } if *open == tag);
+            if let Some(open) = stack.iter().rposition(is_open) {
+                while stack.len() > open {
+                    close_element(&mut stack);
+                }
+            }
+            rest = &rest[close.get(0).unwrap().end()..];
+        } else if let Some(open) = open_tag_regex.captures(rest) {
+            let tag = open[1].to_string();
+            // Component names in JSX keep their case
+            let tag = match tag.starts_with(|c: char| c.is_ascii_uppercase()) {
+                true => tag,
+                false => tag.to_lowercase(),
+            };
+            let attributes = attribute_regex
+                .captures_iter(&open[2])
+                .filter_map(|attribute| {
+                    let name = html_attribute(&attribute[1])?;
+                    let value = (2..=4)
+                        .find_map(|group| attribute.get(group))
+                        .map_or_else(|| "true".to_string(), |value| value.as_str().to_string());
+                    Some((name, value))
+                })
+                .collect();
+            rest = &rest[open.get(0).unwrap().end()..];
+            let self_closing = &open[3] == "/" || VOID_TAGS.contains(&tag.as_str());
+            if !self_closing && DROPPED_TAGS.contains(&tag.as_str()) {
+                // The contents of scripts and styles are not HTML
+                let end = rest
+                    .to_ascii_lowercase()
+                    .find(&format!("</{tag}"))
+                    .unwrap_or(rest.len());
+                rest = &rest[end..];
+            }
+            let element = Node::Element {
+                tag,
+                attributes,
+                children: Vec::new(),
+            };
+            if self_closing {
+                push_node(&mut stack, element);
+            } else {
+                stack.push(element);
+            }
+        } else {
+            // A `<` that does not start a tag is text
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| *c == '<')
+                .map_or(rest.len(), |(end, _)| end);
+            let text = collapse_whitespace(&rest[..end]);
+            if !text.trim().is_empty() {
+                push_node(&mut stack, Node::Text(text));
+            }
+            rest = &rest[end..];
+        }
+    }
+    while stack.len() > 1 {
+        close_element(&mut stack);
+    }
+    match stack.pop() {
+        Some(Node::Element { children, .. }) => children,
+        _ => Vec::new(),
+    }
+}
+
+fn push_node(stack: &mut [Node], node: Node) {
+    if let Some(Node::Element { children, .. }) = stack.last_mut() {
+        children.push(node);
+    }
+}
+
+fn close_element(stack: &mut Vec<Node>) {
+    if let Some(element) = stack.pop() {
+        let dropped =
+            matches!(&element, Node::Element { tag, .. } if DROPPED_TAGS.contains(&tag.as_str()));
+        if !dropped {
+            push_node(stack, element);
+        }
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let whitespace_regex = Regex::new(r"\s+").unwrap();
+    whitespace_regex.replace_all(text, " ").to_string()
+}
+
+// The HTML name of a JSX attribute, or `None` if the attribute does not render anything
+fn html_attribute(name: &str) -> Option<String> {
+    let is_handler = name
+        .strip_prefix("on")
+        .is_some_and(|event| event.starts_with(|c: char| c.is_ascii_uppercase()));
+    if is_handler || matches!(name, "key" | "ref" | "dangerouslySetInnerHTML") {
+        return None;
+    }
+    let name = match name {
+        "className" => "class".to_string(),
+        "htmlFor" => "for".to_string(),
+        _ if !name.chars().any(|c| c.is_ascii_uppercase()) => name.to_string(),
+        _ if CAMEL_CASE_ATTRIBUTES.contains(&name) => name.to_string(),
+        _ if KEBAB_CASE_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix)) =>
+        {
+            name.to_case(Case::Kebab)
+        }
+        _ => name.to_lowercase(),
+    };
+    Some(name)
+}
+
+// The children of the body, or the whole page if there is no body
+fn page_body(nodes: Vec<Node>) -> Vec<Node> {
+    fn find_body(nodes: &[Node]) -> Option<&Vec<Node>> {
+        nodes.iter().find_map(|node| match node {
+            Node::Element { tag, children, .. } if tag == "body" => Some(children),
+            Node::Element { children, .. } => find_body(children),
+            _ => None,
+        })
+    }
+    if let Some(body) = find_body(&nodes) {
+        return body.clone();
+    }
+    nodes
+        .into_iter()
+        .flat_map(|node| match node {
+            Node::Element { tag, children, .. } if tag == "html" => children,
+            node => vec![node],
+        })
+        .collect()
+}
+
+fn page_title(source: &str) -> Option<String> {
+    let title_regex = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap();
+    let title = title_regex.captures(source)?;
+    Some(collapse_whitespace(&title[1]).trim().to_string())
+}
+
+/// Rewrite the JSX expressions in a page to HTML. Strings and template literals become text, identifiers like `{props.title}` become placeholders, style objects become inline styles, and any other expression is removed.
+fn jsx_to_html(source: &str) -> String {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut output = String::new();
+    let mut in_tag = false;
+    let mut quote = None;
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        let next = chars.get(index + 1).copied();
+        if let Some(open) = quote {
+            output.push(c);
+            if c == open {
+                quote = None;
+            }
+            index += 1;
+            continue;
+        }
+        match c {
+            '"' | '\'' if in_tag => {
+                quote = Some(c);
+                output.push(c);
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                output.push(c);
+            }
+            '{' => {
+                let end = matching_brace(&chars, index);
+                let expression = chars[index + 1..end].iter().collect::<String>();
+                if in_tag {
+                    match attribute_expression(&output, &expression) {
+                        Some(value) => output += &format!("\"{}\"", value.replace('"', "&quot;")),
+                        // Remove the name of the attribute
+                        None => {
+                            let name_start = output
+                                .trim_end_matches('=')
+                                .trim_end()
+                                .rfind(char::is_whitespace)
+                                .unwrap_or(output.len());
+                            output.truncate(name_start);
+                        }
+                    }
+                } else {
+                    output += &text_expression(&expression);
+                }
+                index = end + 1;
+                continue;
+            }
+            // Fragments do not render an element
+            '<' if next == Some('>') => {
+                index += 2;
+                continue;
+            }
+            '<' if next == Some('/') && chars.get(index + 2) == Some(&'>') => {
+                index += 3;
+                continue;
+            }
+            '<' if next.is_some_and(|next| next.is_ascii_alphabetic() || next == '/') => {
+                in_tag = true;
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+        index += 1;
+    }
+    output
+}
+
+// The index of the brace that closes the brace at `start`. Braces in strings are skipped
+fn matching_brace(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut index = start;
+    while index < chars.len() {
+        let c = chars[index];
+        match quote {
+            Some(_) if c == '\\' => index += 1,
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return index;
+                    }
+                }
+                _ => {}
+            },
+        }
+        index += 1;
+    }
+    chars.len().saturating_sub(1).max(start)
+}
+
+fn text_expression(expression: &str) -> String {
+    let expression = expression.trim();
+    if expression.starts_with("/*") {
+        return String::new();
+    }
+    if let Some(text) = string_literal(expression) {
+        return text;
+    }
+    match placeholder(expression) {
+        Some(name) => format!("{{{name}}}"),
+        None => String::new(),
+    }
+}
+
+// The value of an attribute set to an expression. `output` is the HTML written so far, which ends with the attribute name
+fn attribute_expression(output: &str, expression: &str) -> Option<String> {
+    let expression = expression.trim();
+    let name = output
+        .trim_end_matches('=')
+        .trim_end()
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or_default();
+    if name == "style" {
+        if let Some(object) = expression
+            .strip_prefix('{')
+            .and_then(|object| object.strip_suffix('}'))
+        {
+            return Some(style_object(object));
+        }
+    }
+    if let Some(text) = string_literal(expression) {
+        return Some(text);
+    }
+    if matches!(expression, "true" | "false") || expression.parse::<f64>().is_ok() {
+        return Some(expression.to_string());
+    }
+    placeholder(expression).map(|name| format!("{{{name}}}"))
+}
+
+// The text of a string or template literal. Interpolations in template literals become placeholders
+fn string_literal(expression: &str) -> Option<String> {
+    let quote = expression.chars().next()?;
+    if !matches!(quote, '"' | '\'' | '`') || expression.len() < 2 || !expression.ends_with(quote) {
+        return None;
+    }
+    let text = &expression[1..expression.len() - 1];
+    if quote != '`' {
+        return Some(text.to_string());
+    }
+    let interpolation_regex = Regex::new(r"\$\{([^}]*)\}").unwrap();
+    Some(
+        interpolation_regex
+            .replace_all(text, |interpolation: &regex::Captures| {
+                placeholder(&interpolation[1])
+                    .map(|name| format!("{{{name}}}"))
+                    .unwrap_or_default()
+            })
+            .to_string(),
+    )
+}
+
+// The placeholder name for an expression that only reads a value, like `title` or `props.user.name`
+fn placeholder(expression: &str) -> Option<String> {
+    let path_regex = Regex::new(r"^[A-Za-z_$][\w$]*(?:\??\.[A-Za-z_$][\w$]*)*$").unwrap();
+    let expression = expression.trim();
+    if !path_regex.is_match(expression) {
+        return None;
+    }
+    let path = expression
+        .strip_prefix("props.")
+        .or_else(|| expression.strip_prefix("this.props."))
+        .unwrap_or(expression);
+    Some(prop_name(&path.replace(['?', '$'], "")))
+}
+
+// Turn a JSX style object like `{ marginTop: 8, color: "red" }` into CSS
+fn style_object(object: &str) -> String {
+    object
+        .split(',')
+        .filter_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            let property = property
+                .trim()
+                .trim_matches(|c| matches!(c, '"' | '\''))
+                .to_case(Case::Kebab);
+            let value = value.trim();
+            let value = match string_literal(value) {
+                Some(value) => value,
+                None if value.parse::<f64>().is_ok()
+                    && value != "0"
+                    && !UNITLESS_STYLES.contains(&property.as_str()) =>
+                {
+                    format!("{value}px")
+                }
+                None => value.to_string(),
+            };
+            Some(format!("{property}: {value}"))
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[test]
+fn componentize_repeated_cards() {
+    let jsx = r#"<>
+  <section className="grid gap-4">
+    {/* Features */}
+    <div className="rounded p-4" style={{ marginTop: 8, color: "red" }}>
+      <img src="/fast.svg" alt="Fast" />
+      <h2 className="font-bold">{`Fast`}</h2>
+      <p>Loads in a blink</p>
+      <button onClick={() => alert("hi")}>Learn more</button>
+    </div>
+    <div className="rounded p-4" style={{ marginTop: 8, color: "red" }}>
+      <img src="/safe.svg" alt="Safe" />
+      <h2 className="font-bold">Safe</h2>
+      <p>{props.safetyNote}</p>
+      <button>Learn more</button>
+    </div>
+  </section>
+  <ul><li><a href="/">Home</a></li><li><a href="/docs">Docs</a></li></ul>
+  <h1>{"Welcome"}</h1>
+</>"#;
+    let state = PartialState::componentize(jsx);
+    assert_eq!(
+        state.html,
+        "<section class=\"grid gap-4\"><Card image_url=\"/fast.svg\" image_alt=\"Fast\" title=\"Fast\" description=\"Loads in a blink\"/><Card image_url=\"/safe.svg\" image_alt=\"Safe\" title=\"Safe\" description=\"{safety_note}\"/></section><ul><ListItem url=\"/\" link_text=\"Home\"/><ListItem url=\"/docs\" link_text=\"Docs\"/></ul><h1>Welcome</h1>"
+    );
+    let components = state.components();
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[0].name, "Card");
+    assert_eq!(
+        components[0].html,
+        "<div class=\"rounded p-4\" style=\"margin-top: 8px; color: red\"><img src=\"{image_url}\" alt=\"{image_alt}\"><h2 class=\"font-bold\">{title}</h2><p>{description}</p><button>Learn more</button></div>"
+    );
+    assert_eq!(components[1].name, "ListItem");
+    assert_eq!(
+        components[1].html,
+        "<li><a href=\"{url}\">{link_text}</a></li>"
+    );
+}
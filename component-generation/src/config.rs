@@ -0,0 +1,145 @@
+use crate::ConfigError;
+use kalosm::language::*;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Where to load the model from and how to sample from it.
+///
+/// Every field is optional. Missing model files are downloaded from huggingface and missing sampling parameters use the defaults from kalosm.
+///
+/// A config can be loaded from a TOML file:
+///
+/// ```toml
+/// model = "models/llama3-v2.Q4_K_M.gguf"
+/// tokenizer = "models/tokenizer.json"
+/// temperature = 0.4
+/// top_p = 0.9
+/// seed = 1234
+/// max_chunks = 4096
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GenerationConfig {
+    /// A local GGUF file with the model weights
+    pub model: Option<PathBuf>,
+    /// A local tokenizer.json file for the model
+    pub tokenizer: Option<PathBuf>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f64>,
+    /// The seed for the sampler. Each request to the model uses the next seed after the previous request, so a new backend with the same seed produces the same UI for the same prompts. Repairs still get a different response than the first attempt.
+    pub seed: Option<u64>,
+    /// Stop each response after the model streamed this many chunks of text. A chunk is usually one token, but text the grammar forces can arrive as one longer chunk. A response that is cut off fails with [`GenerationError::Truncated`](crate::GenerationError::Truncated)
+    pub max_chunks: Option<usize>,
+}
+
+impl GenerationConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a config from a TOML file. Relative model and tokenizer paths are resolved from the directory of the file.
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let mut config = Self::from_toml_str(&std::fs::read_to_string(path)?)?;
+        if let Some(dir) = path.parent() {
+            config.model = config.model.map(|model| dir.join(model));
+            config.tokenizer = config.tokenizer.map(|tokenizer| dir.join(tokenizer));
+        }
+        Ok(config)
+    }
+
+    /// Parse a config from TOML
+    pub fn from_toml_str(toml: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    pub fn with_model(mut self, model: impl Into<PathBuf>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn with_tokenizer(mut self, tokenizer: impl Into<PathBuf>) -> Self {
+        self.tokenizer = Some(tokenizer.into());
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn with_top_p(mut self, top_p: f64) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_max_chunks(mut self, max_chunks: usize) -> Self {
+        self.max_chunks = Some(max_chunks);
+        self
+    }
+
+    /// The model and tokenizer files. Files that are not set are downloaded from huggingface.
+    pub(crate) fn source(&self) -> LlamaSource {
+        let model = match &self.model {
+            Some(path) => FileSource::local(path.clone()),
+            None => FileSource::huggingface(
+                "Demonthos/llama3".to_string(),
+                "3387b74827b8429717e7e955efe4eaaea061e178".to_string(),
+                "llama3-v2.Q4_K_M.gguf".to_string(),
+            ),
+        };
+        let tokenizer = match &self.tokenizer {
+            Some(path) => FileSource::local(path.clone()),
+            None => FileSource::huggingface(
+                "NousResearch/Meta-Llama-3-8B".to_string(),
+                "main".to_string(),
+                "tokenizer.json".to_string(),
+            ),
+        };
+        LlamaSource::new(model, tokenizer)
+    }
+
+    /// The sampling parameters for the nth request to the model, or `None` if every parameter uses the default
+    pub(crate) fn parameters(&self, request: u64) -> Option<GenerationParameters> {
+        if self.temperature.is_none() && self.top_p.is_none() && self.seed.is_none() {
+            return None;
+        }
+        let mut parameters = GenerationParameters::default();
+        if let Some(temperature) = self.temperature {
+            parameters = parameters.with_temperature(temperature);
+        }
+        if let Some(top_p) = self.top_p {
+            parameters = parameters.with_top_p(top_p);
+        }
+        if let Some(seed) = self.seed {
+            parameters = parameters.with_seed(seed.wrapping_add(request));
+        }
+        Some(parameters)
+    }
+}
+
+#[test]
+fn parse_toml_config() {
+    let config = GenerationConfig::from_toml_str(
+        r#"
+model = "models/llama3.gguf"
+temperature = 0.2
+seed = 7
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        GenerationConfig::new()
+            .with_model("models/llama3.gguf")
+            .with_temperature(0.2)
+            .with_seed(7)
+    );
+
+    assert!(GenerationConfig::from_toml_str("temprature = 0.2").is_err());
+}
@@ -0,0 +1,263 @@
+use crate::{
+    GenerationError, GenerationEvent, KalosmBackend, ModelBackend, OutputGrammar, PartialState,
+    UiGenerator,
+};
+use convert_case::{Case, Casing};
+use futures_util::{Stream, StreamExt};
+
+/// Change a UI that was already generated with the default model.
+pub async fn edit_ui(
+    state: &PartialState,
+    instruction: &str,
+) -> Result<PartialState, GenerationError> {
+    UiGenerator::new(KalosmBackend::default_model().await?)
+        .edit(state, instruction)
+        .await
+}
+
+impl<B: ModelBackend> UiGenerator<B> {
+    /// Change a UI that was already generated and wait for the edit to finish.
+    pub async fn edit(
+        &self,
+        state: &PartialState,
+        instruction: &str,
+    ) -> Result<PartialState, GenerationError> {
+        let mut events = Box::pin(self.edit_stream(state, instruction));
+        while let Some(event) = events.next().await {
+            if let GenerationEvent::Finished(state) = event? {
+                return Ok(state);
+            }
+        }
+        unreachable!("the event stream always ends with a finished event")
+    }
+
+    /// Change a UI that was already generated and stream events as each changed part is finished.
+    ///
+    /// The model sees the current description, component list and HTML next to the instruction. If every part of the instruction names an existing component, only the HTML of those components is regenerated. Otherwise the description, component list and top level HTML are regenerated, components the instruction doesn't name keep their HTML, and only new and named components get new HTML.
+    pub fn edit_stream(
+        &self,
+        state: &PartialState,
+        instruction: &str,
+    ) -> impl Stream<Item = Result<GenerationEvent, GenerationError>> + '_ {
+        let mentioned = mentioned_components(state, instruction);
+        let only_components = !mentioned.is_empty()
+            && clauses(instruction).all(|clause| !mentioned_components(state, clause).is_empty());
+        let prompt = format!("{}\n\nChanges: {}", state.prompt.trim(), instruction.trim());
+        let mut previous = state.clone();
+        previous.prompt = edit_prompt(state, instruction);
+
+        async_stream::stream! {
+            let mut state;
+            if only_components {
+                state = previous;
+                for index in mentioned {
+                    let html = match self.component_html(&state, index).await {
+                        Ok(html) => html,
+                        Err(err) => {
+                            yield Err(err);
+                            return;
+                        }
+                    };
+                    let component = &mut state.components[index];
+                    component.html = html;
+                    yield Ok(GenerationEvent::ComponentHtmlComplete {
+                        name: component.name.clone(),
+                        html: component.html.clone(),
+                    });
+                }
+            } else {
+                state = PartialState::new(previous.prompt.clone(), previous.target);
+                {
+                    let mut sections = Box::pin(self.stream_sections(
+                        &mut state,
+                        "\nDESCRIPTION:\n",
+                        &OutputGrammar::sections().with_chat_template(self.chat_template),
+                    ));
+                    while let Some(event) = sections.next().await {
+                        let failed = event.is_err();
+                        yield event;
+                        if failed {
+                            return;
+                        }
+                    }
+                }
+
+                // Components the instruction names get new HTML. The others keep their HTML
+                let kept = previous
+                    .components
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !mentioned.contains(index))
+                    .map(|(_, component)| component.clone())
+                    .collect::<Vec<_>>();
+                for await event in self.stream_component_html(&mut state, &kept) {
+                    yield event;
+                }
+            }
+            state.prompt = prompt;
+
+            for await event in self.finish(state) {
+                yield event;
+            }
+        }
+    }
+}
+
+// The prompt for an edit with the current UI written in the format of a response, so the model can change it instead of starting over
+fn edit_prompt(state: &PartialState, instruction: &str) -> String {
+    let mut current = format!("DESCRIPTION:\n{}\ncomponents:\n", state.description);
+    for component in &state.components {
+        current += &format!("- {}: {}\n", component.name, component.description);
+    }
+    let interactive = state
+        .components
+        .iter()
+        .filter(|component| !component.state.is_empty())
+        .map(|component| format!("- {}: {}\n", component.name, component.state.join(", ")))
+        .collect::<String>();
+    if !interactive.is_empty() {
+        current += &format!("interactive:\n{interactive}");
+    }
+    current += &format!("HTML:\n{}\ncomponent html:\n", state.html);
+    for component in &state.components {
+        current += &format!("{}:\n{}\n", component.name, component.html);
+    }
+    format!(
+        "{}\n\nThis is the current UI:\n{current}\nChanges: {}",
+        state.prompt.trim(),
+        instruction.trim()
+    )
+}
+
+// The parts of an instruction like "make the header sticky and add a search box"
+fn clauses(instruction: &str) -> impl Iterator<Item = &str> {
+    instruction
+        .split([',', ';', '.', '!', '\n'])
+        .flat_map(|clause| clause.split(" and "))
+        .filter(|clause| clause.chars().any(char::is_alphanumeric))
+}
+
+// Find the components the instruction refers to by name. `NavBar` matches both "nav bar" and "navbar", and `PageHeader` also matches "header" if no other component ends with that word
+fn mentioned_components(state: &PartialState, instruction: &str) -> Vec<usize> {
+    let instruction = format!(
+        " {} ",
+        instruction
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    );
+
+    state
+        .components
+        .iter()
+        .enumerate()
+        .filter(|(_, component)| {
+            let words = component.name.to_case(Case::Lower);
+            let joined = words.replace(' ', "");
+            let last = words.rsplit(' ').next().unwrap_or_default();
+            let last_is_unique = state
+                .components
+                .iter()
+                .filter(|other| other.name.to_case(Case::Lower).ends_with(last))
+                .count()
+                == 1;
+            instruction.contains(&format!(" {words} "))
+                || instruction.contains(&format!(" {joined} "))
+                || (last.len() >= 4 && last_is_unique && instruction.contains(&format!(" {last} ")))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[tokio::test]
+async fn edit_mentioned_component() {
+    use crate::ScriptedBackend;
+
+    let backend = ScriptedBackend::new()
+        .with_response(
+            "A page with a header.
+components:
+- PageHeader: The header of the page
+- Footer: The footer of the page
+HTML:
+<div><PageHeader/><Footer/></div>
+component html:
+PageHeader:
+<header class=\"p-4\"></header>
+Footer:
+<footer class=\"p-4\"></footer><|eot_id|>",
+        )
+        .with_response("<header class=\"sticky top-0 p-4\"><input type=\"search\"/></header>\n")
+        .with_response(
+            "A page with a header and a banner.
+components:
+- PageHeader: The header of the page
+- Banner: A sale banner
+- Footer: The footer of the page
+HTML:
+<div><PageHeader/><Banner/><Footer/></div>
+",
+        )
+        .with_response("<div class=\"bg-yellow-100\">Sale</div>\n")
+        .with_response("<footer class=\"bg-black p-4\"></footer>\n");
+    let generator = UiGenerator::new(backend.clone());
+    let state = generator.generate("a page").await.unwrap();
+
+    assert_eq!(
+        mentioned_components(&state, "make the page header sticky"),
+        [0]
+    );
+    assert_eq!(mentioned_components(&state, "Change the pageheader!"), [0]);
+    assert_eq!(mentioned_components(&state, "make the header sticky"), [0]);
+    assert_eq!(
+        mentioned_components(&state, "add a search box to the page"),
+        Vec::<usize>::new()
+    );
+
+    // Every part of the instruction names the header, so only the header is regenerated
+    let edited = generator
+        .edit(&state, "make the header sticky with a search box")
+        .await
+        .unwrap();
+    assert_eq!(edited.description, state.description);
+    assert_eq!(edited.html, state.html);
+    assert_eq!(
+        edited.components()[0].html,
+        "<header class=\"sticky top-0 p-4\"><input type=\"search\"/></header>"
+    );
+    assert_eq!(edited.components()[1].html, state.components()[1].html);
+    assert_eq!(
+        edited.prompt(),
+        "a page\n\nChanges: make the header sticky with a search box"
+    );
+    // The model sees the HTML it is changing
+    let prompt = &backend.prompts()[1];
+    assert!(prompt.contains("This is the current UI:\nDESCRIPTION:\nA page with a header.\n"));
+    assert!(prompt.contains("PageHeader:\n<header class=\"p-4\"></header>\n"));
+    assert!(prompt.contains("Changes: make the header sticky with a search box"));
+
+    // Adding a banner changes the top level HTML, so the sections are regenerated as well as the footer
+    let edited = generator
+        .edit(&edited, "make the footer dark and add a sale banner")
+        .await
+        .unwrap();
+    assert_eq!(edited.html, "<div><PageHeader/><Banner/><Footer/></div>");
+    let html = edited
+        .components()
+        .iter()
+        .map(|component| component.html.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        html,
+        [
+            "<header class=\"sticky top-0 p-4\"><input type=\"search\"/></header>",
+            "<div class=\"bg-yellow-100\">Sale</div>",
+            "<footer class=\"bg-black p-4\"></footer>",
+        ]
+    );
+    assert!(backend.prompts()[2].contains(
+        "HTML:\n<div><PageHeader/><Footer/></div>\ncomponent html:\nPageHeader:\n<header class=\"sticky top-0 p-4\">"
+    ));
+}
@@ -0,0 +1,90 @@
+/// An error that happened while generating or converting a UI. Each error keeps the model response or HTML that caused it so callers can retry or show it.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum GenerationError {
+    /// The model wrote HTML for a component it never declared
+    #[error("component {name} was not declared. Declared components: {declared:?}")]
+    UnknownComponent {
+        name: String,
+        declared: Vec<String>,
+        raw_output: String,
+    },
+    /// A line in the component list was not in the `- Name: description` format
+    #[error("malformed component declaration: {line}")]
+    MalformedComponent { line: String, raw_output: String },
+    /// A line in the page list of a site was not in the `- Name: description` format
+    #[error("malformed page declaration: {line}")]
+    MalformedPage { line: String, raw_output: String },
+    /// The model did not list any pages for a site
+    #[error("the site has no pages")]
+    NoPages { raw_output: String },
+    /// The response ended before it was complete, usually because it reached the `max_chunks` limit of the [`GenerationConfig`](crate::GenerationConfig)
+    #[error("the model output ended before the response was complete")]
+    Truncated { raw_output: String },
+    /// The model could not be loaded
+    #[error("failed to load the model: {message}")]
+    ModelLoad { message: String },
+    /// The HTML could not be parsed
+    #[error("failed to parse HTML: {message}")]
+    InvalidHtml { message: String, html: String },
+    /// The HTML was parsed, but the rsx could not be written out
+    #[error("failed to convert HTML to rsx")]
+    InvalidRsx { html: String },
+}
+
+impl GenerationError {
+    /// The whole model response that caused this error. Errors from converting the HTML of a single component and from loading the model have no response, see [`GenerationError::html`]
+    pub fn raw_output(&self) -> Option<&str> {
+        match self {
+            GenerationError::UnknownComponent { raw_output, .. }
+            | GenerationError::MalformedComponent { raw_output, .. }
+            | GenerationError::MalformedPage { raw_output, .. }
+            | GenerationError::NoPages { raw_output }
+            | GenerationError::Truncated { raw_output } => Some(raw_output),
+            _ => None,
+        }
+    }
+
+    /// The HTML of the component that could not be converted to rsx
+    pub fn html(&self) -> Option<&str> {
+        match self {
+            GenerationError::InvalidHtml { html, .. } | GenerationError::InvalidRsx { html } => {
+                Some(html)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An error that happened while loading a [`GenerationConfig`](crate::GenerationConfig) or the model it points to
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to read the config: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid config: {0}")]
+    Toml(#[from] toml::de::Error),
+    /// The model or tokenizer could not be loaded
+    #[error("failed to load the model: {0}")]
+    Model(String),
+}
+
+/// The generated text does not follow the [`OutputGrammar`](crate::OutputGrammar)
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("unexpected {found:?} in the model output")]
+pub struct GrammarError {
+    pub found: char,
+}
+
+/// An error that stopped a batch from [`UiGenerator::generate_batch`](crate::UiGenerator::generate_batch). Errors from generating a single prompt are written to its result instead.
+#[derive(Debug, thiserror::Error)]
+pub enum BatchError {
+    #[error("failed to read or write the batch: {0}")]
+    Io(#[from] std::io::Error),
+    /// A line in the prompt file is not a JSON string or an object with a prompt
+    #[error("invalid prompt on line {line}: {source}")]
+    InvalidPrompt {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("failed to serialize a result: {0}")]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,141 @@
+use crate::naming::rust_ident;
+use crate::preview::typed_sample_value;
+use crate::{DioxusVersion, GenerationError, PartialState, Project, Prop, PropType};
+
+impl Prop {
+    /// A sample value for the prop as a rust expression, picked from the name and type of the prop. Returns `None` for event handlers and children, which have no sample value.
+    pub fn sample_value(&self, target: DioxusVersion) -> Option<String> {
+        let value = typed_sample_value(&self.name, self.ty, None);
+        match self.ty {
+            PropType::MouseEventHandler | PropType::FormEventHandler => None,
+            PropType::Element if self.name == "children" => None,
+            PropType::Element => Some(target.inline_rsx(&text(&humanize(&self.name)))),
+            PropType::String => Some(format!("{value:?}")),
+            PropType::Float if !value.contains('.') => Some(format!("{value}.0")),
+            PropType::Integer | PropType::Float | PropType::Bool => Some(value),
+        }
+    }
+}
+
+impl PartialState {
+    /// The code of an `app` component that renders every component with sample props and its description as a caption, like a small storybook.
+    pub fn gallery(&self) -> Result<String, GenerationError> {
+        let entries = self
+            .components
+            .iter()
+            .map(|component| {
+                Ok((
+                    component.name.as_str(),
+                    component.description.as_str(),
+                    component.props()?,
+                ))
+            })
+            .collect::<Result<Vec<_>, GenerationError>>()?;
+        Ok(gallery_app(&entries, self.target))
+    }
+
+    /// Create a cargo project like [`PartialState::project`] that launches the [`PartialState::gallery`] instead of the generated UI
+    pub fn gallery_project(&self, name: &str) -> Result<Project, GenerationError> {
+        self.project_with_app(name, PartialState::gallery)
+    }
+}
+
+// Write the gallery for each component name, description and props
+fn gallery_app(components: &[(&str, &str, Vec<Prop>)], target: DioxusVersion) -> String {
+    let mut rsx = String::from("\n        div { class: \"mx-auto max-w-5xl space-y-12 p-8\",");
+    rsx += "\n            h1 { class: \"text-3xl font-bold\", \"Component gallery\" }";
+    for (name, description, props) in components {
+        rsx += "\n            section { class: \"space-y-4\",";
+        rsx += &format!(
+            "\n                h2 {{ class: \"text-xl font-semibold\", {} }}",
+            text(name)
+        );
+        if !description.trim().is_empty() {
+            rsx += &format!(
+                "\n                p {{ class: \"text-gray-500\", {} }}",
+                text(description.trim())
+            );
+        }
+        rsx += "\n                div { class: \"rounded-lg border p-4\",";
+        rsx += &format!("\n                    {name} {{");
+        for prop in props {
+            if let Some(value) = prop.sample_value(target) {
+                rsx += &format!(
+                    "\n                        {}: {value},",
+                    rust_ident(&prop.name)
+                );
+            }
+        }
+        if props.iter().any(|prop| prop.name == "children") {
+            rsx += &format!("\n                        {}", text("Children"));
+        }
+        rsx += "\n                    }\n                }\n            }";
+    }
+    rsx += "\n        }";
+
+    format!(
+        "/// Every component rendered with sample props\n#[component]\n{} {{\n    {}\n}}",
+        target.signature("app", &[], &[]),
+        target.render(&rsx)
+    )
+}
+
+// A text node. Braces are escaped so they are not formatted
+fn text(text: &str) -> String {
+    format!("{:?}", text.replace('{', "{{").replace('}', "}}"))
+}
+
+// "follower_count" becomes "Follower count"
+fn humanize(name: &str) -> String {
+    let text = name.replace('_', " ");
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[test]
+fn gallery_renders_samples() {
+    use crate::validate::validate_code;
+
+    let prop = |name: &str, ty, optional| Prop {
+        name: name.to_string(),
+        ty,
+        optional,
+    };
+    let props = vec![
+        prop("user_name", PropType::String, false),
+        prop("avatar_url", PropType::String, false),
+        prop("follower_count", PropType::Integer, false),
+        prop("progress", PropType::Float, false),
+        prop("is_verified", PropType::Bool, true),
+        prop("header", PropType::Element, true),
+        prop("on_follow", PropType::MouseEventHandler, true),
+        prop("children", PropType::Element, false),
+        prop("type", PropType::String, false),
+    ];
+    let components = [("UserCard", "A card for a {user}", props)];
+
+    let code = gallery_app(&components, DioxusVersion::V0_5);
+    assert!(code.contains("pub fn app() -> Element {\n    rsx! {"));
+    assert!(code.contains("p { class: \"text-gray-500\", \"A card for a {{user}}\" }"));
+    assert!(code.contains(
+        "UserCard {
+                        user_name: \"Jane Doe\",
+                        avatar_url: \"https://placehold.co/600x400\",
+                        follower_count: 3,
+                        progress: 60.0,
+                        is_verified: true,
+                        header: rsx! { \"Header\" },
+                        r#type: \"Type\",
+                        \"Children\"
+                    }"
+    ));
+    assert_eq!(validate_code(&code), []);
+
+    let code = gallery_app(&components, DioxusVersion::V0_4);
+    assert!(code.contains("pub fn app(cx: Scope) -> Element {\n    cx.render(rsx! {"));
+    assert!(code.contains("header: cx.render(rsx! { \"Header\" }),"));
+    assert_eq!(validate_code(&code), []);
+}
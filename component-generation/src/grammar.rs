@@ -0,0 +1,766 @@
+use crate::props::BOOLEAN_ATTRIBUTES;
+use crate::{ChatTemplate, GrammarError};
+
+/// The grammar of a model response.
+///
+/// The grammar follows the output format the model was trained on, but every HTML line must be well-formed: tags are balanced, tag names are known HTML tags and every attribute value is quoted. Only boolean attributes like `disabled` can be written without a value. Uppercase tags are only accepted for components that were already declared, so the model can't render a component it never declared or a component inside itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputGrammar {
+    kind: GrammarKind,
+    components: Vec<String>,
+    current: Option<String>,
+    template: ChatTemplate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GrammarKind {
+    /// The description, component list, top level HTML and the HTML of every component
+    Ui,
+    /// The description, component list and top level HTML
+    Sections,
+    /// The HTML of a single component
+    ComponentHtml,
+    /// A list of pages like the component list
+    Pages,
+}
+
+impl OutputGrammar {
+    /// A full response with the HTML of every declared component, ending with the stop token of the chat template
+    pub fn ui() -> Self {
+        Self {
+            kind: GrammarKind::Ui,
+            components: Vec::new(),
+            current: None,
+            template: ChatTemplate::default(),
+        }
+    }
+
+    /// The description, component list and top level HTML, ending with a newline
+    pub fn sections() -> Self {
+        Self {
+            kind: GrammarKind::Sections,
+            ..Self::ui()
+        }
+    }
+
+    /// The HTML of the component `name` on a single line, ending with a newline or the stop token of the chat template. The HTML can render any of the other components.
+    pub fn component_html(
+        components: impl IntoIterator<Item = impl Into<String>>,
+        name: &str,
+    ) -> Self {
+        Self {
+            kind: GrammarKind::ComponentHtml,
+            components: components.into_iter().map(Into::into).collect(),
+            current: Some(name.to_string()),
+            template: ChatTemplate::default(),
+        }
+    }
+
+    /// End the response with the stop token of this chat template where it can end. Defaults to [`ChatTemplate::Llama3`].
+    pub fn with_chat_template(mut self, template: ChatTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// A list of pages in the same `- Name: description` format as the component list, ending with an empty line
+    pub fn pages() -> Self {
+        Self {
+            kind: GrammarKind::Pages,
+            ..Self::ui()
+        }
+    }
+
+    /// The state of the grammar before any text is generated
+    pub fn state(&self) -> GrammarState {
+        let stage = match self.kind {
+            GrammarKind::Ui | GrammarKind::Sections => Stage::Description { empty: true },
+            GrammarKind::ComponentHtml => Stage::Html,
+            GrammarKind::Pages => Stage::ComponentStart { first: true },
+        };
+        GrammarState {
+            kind: self.kind,
+            stage,
+            declared: self.components.clone(),
+            written: Vec::new(),
+            current: self.current.clone(),
+            template: self.template,
+            html: HtmlState::default(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns true if the text is a complete response that follows the grammar
+    pub fn accepts(&self, text: &str) -> bool {
+        let mut state = self.state();
+        state.push_str(text).is_ok() && state.is_finished()
+    }
+}
+
+/// The progress through an [`OutputGrammar`] after some text was generated.
+///
+/// If pushing text fails, the text does not follow the grammar and the state should be discarded.
+#[derive(Debug, Clone)]
+pub struct GrammarState {
+    kind: GrammarKind,
+    stage: Stage,
+    /// The components declared so far
+    declared: Vec<String>,
+    /// The components that already have HTML
+    written: Vec<String>,
+    /// The component whose HTML is being written
+    current: Option<String>,
+    template: ChatTemplate,
+    html: HtmlState,
+    // The bytes of a character that is not finished yet
+    pending: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stage {
+    Description { empty: bool },
+    Literal { literal: Literal, matched: usize },
+    ComponentStart { first: bool },
+    ComponentDash,
+    ComponentName(String),
+    ComponentDescriptionStart,
+    ComponentDescription { empty: bool },
+    InteractiveStart { first: bool },
+    InteractiveDash,
+    InteractiveName(String),
+    InteractiveNameEnd,
+    InteractiveState { empty: bool },
+    InteractiveSeparator,
+    Html,
+    ComponentHeader(String),
+    ComponentHeaderEnd,
+    Done,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Literal {
+    Components,
+    Interactive,
+    Html,
+    ComponentHtml,
+    Eot,
+}
+
+impl Literal {
+    fn text(self, template: ChatTemplate) -> &'static str {
+        match self {
+            Literal::Components => "components:\n",
+            Literal::Interactive => "interactive:\n",
+            Literal::Html => "HTML:\n",
+            Literal::ComponentHtml => "component html:\n",
+            Literal::Eot => template.stop_token(),
+        }
+    }
+}
+
+impl GrammarState {
+    /// Returns true once the response is complete. No more text is accepted after that.
+    pub fn is_finished(&self) -> bool {
+        self.stage == Stage::Done
+    }
+
+    /// Text that must come next, if there is only one way to continue
+    pub fn required_next(&self) -> &'static str {
+        match &self.stage {
+            Stage::Literal { literal, matched } if self.pending.is_empty() => {
+                &literal.text(self.template)[*matched..]
+            }
+            _ => "",
+        }
+    }
+
+    pub fn push_str(&mut self, text: &str) -> Result<(), GrammarError> {
+        for c in text.chars() {
+            self.push_char(c)?;
+        }
+        Ok(())
+    }
+
+    /// Push a single byte of UTF-8 text. Characters are checked once all of their bytes are pushed.
+    pub fn push_byte(&mut self, byte: u8) -> Result<(), GrammarError> {
+        self.pending.push(byte);
+        match std::str::from_utf8(&self.pending) {
+            Ok(text) => {
+                let c = text.chars().next().unwrap();
+                self.pending.clear();
+                self.push_char(c)
+            }
+            Err(err) if err.error_len().is_none() => Ok(()),
+            Err(_) => Err(GrammarError {
+                found: char::REPLACEMENT_CHARACTER,
+            }),
+        }
+    }
+
+    fn push_char(&mut self, c: char) -> Result<(), GrammarError> {
+        let error = GrammarError { found: c };
+        self.stage = match std::mem::replace(&mut self.stage, Stage::Done) {
+            Stage::Description { empty } => match c {
+                '\n' if !empty => Stage::Literal {
+                    literal: Literal::Components,
+                    matched: 0,
+                },
+                '\n' => return Err(error),
+                _ => Stage::Description { empty: false },
+            },
+            Stage::Literal { literal, matched } => {
+                let text = literal.text(self.template);
+                if !text[matched..].starts_with(c) {
+                    return Err(error);
+                }
+                let matched = matched + c.len_utf8();
+                if matched < text.len() {
+                    Stage::Literal { literal, matched }
+                } else {
+                    match literal {
+                        Literal::Components => Stage::ComponentStart { first: true },
+                        Literal::Interactive => Stage::InteractiveStart { first: true },
+                        Literal::Html => {
+                            self.html = HtmlState::default();
+                            Stage::Html
+                        }
+                        Literal::ComponentHtml => Stage::ComponentHeader(String::new()),
+                        Literal::Eot => Stage::Done,
+                    }
+                }
+            }
+            Stage::ComponentStart { first } => match c {
+                '-' => Stage::ComponentDash,
+                '\n' if !first && self.kind == GrammarKind::Pages => Stage::Done,
+                _ if self.kind == GrammarKind::Pages => return Err(error),
+                'H' if !first => Stage::Literal {
+                    literal: Literal::Html,
+                    matched: 1,
+                },
+                'i' if !first => Stage::Literal {
+                    literal: Literal::Interactive,
+                    matched: 1,
+                },
+                _ => return Err(error),
+            },
+            Stage::ComponentDash => match c {
+                ' ' => Stage::ComponentName(String::new()),
+                _ => return Err(error),
+            },
+            Stage::ComponentName(mut name) => match c {
+                ':' if name.len() >= 3 && !self.declared.contains(&name) => {
+                    self.declared.push(name);
+                    Stage::ComponentDescriptionStart
+                }
+                _ if is_component_name_char(name.len(), c) => {
+                    name.push(c);
+                    Stage::ComponentName(name)
+                }
+                _ => return Err(error),
+            },
+            Stage::ComponentDescriptionStart => match c {
+                ' ' => Stage::ComponentDescription { empty: true },
+                _ => return Err(error),
+            },
+            Stage::ComponentDescription { empty } => match c {
+                '\n' if !empty => Stage::ComponentStart { first: false },
+                _ if c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | ' ') => {
+                    Stage::ComponentDescription { empty: false }
+                }
+                _ => return Err(error),
+            },
+            // The optional interactive section lists the state of components like `- Tabs: selected_tab, is_open`
+            Stage::InteractiveStart { first } => match c {
+                '-' => Stage::InteractiveDash,
+                'H' if !first => Stage::Literal {
+                    literal: Literal::Html,
+                    matched: 1,
+                },
+                _ => return Err(error),
+            },
+            Stage::InteractiveDash => match c {
+                ' ' => Stage::InteractiveName(String::new()),
+                _ => return Err(error),
+            },
+            Stage::InteractiveName(mut name) => match c {
+                ':' if self.declared.contains(&name) => Stage::InteractiveNameEnd,
+                _ => {
+                    name.push(c);
+                    if !self
+                        .declared
+                        .iter()
+                        .any(|declared| declared.starts_with(&name))
+                    {
+                        return Err(error);
+                    }
+                    Stage::InteractiveName(name)
+                }
+            },
+            Stage::InteractiveNameEnd => match c {
+                ' ' => Stage::InteractiveState { empty: true },
+                _ => return Err(error),
+            },
+            Stage::InteractiveState { empty } => match c {
+                ',' if !empty => Stage::InteractiveSeparator,
+                '\n' if !empty => Stage::InteractiveStart { first: false },
+                _ if c.is_ascii_lowercase() || c == '_' || (!empty && c.is_ascii_digit()) => {
+                    Stage::InteractiveState { empty: false }
+                }
+                _ => return Err(error),
+            },
+            Stage::InteractiveSeparator => match c {
+                ' ' => Stage::InteractiveState { empty: true },
+                _ => return Err(error),
+            },
+            Stage::Html => match self.push_html(c)? {
+                HtmlStep::Continue => Stage::Html,
+                HtmlStep::LineEnd => match (self.kind, &self.current) {
+                    (GrammarKind::Ui, None) => Stage::Literal {
+                        literal: Literal::ComponentHtml,
+                        matched: 0,
+                    },
+                    (GrammarKind::Ui, Some(_)) if self.written.len() < self.declared.len() => {
+                        Stage::ComponentHeader(String::new())
+                    }
+                    (GrammarKind::Ui, Some(_)) => return Err(error),
+                    (GrammarKind::Sections | GrammarKind::ComponentHtml, _) => Stage::Done,
+                    (GrammarKind::Pages, _) => return Err(error),
+                },
+                HtmlStep::Eot => match (self.kind, &self.current) {
+                    (GrammarKind::Ui, Some(_)) if self.written.len() == self.declared.len() => {
+                        Stage::Literal {
+                            literal: Literal::Eot,
+                            matched: 1 + c.len_utf8(),
+                        }
+                    }
+                    (GrammarKind::ComponentHtml, _) => Stage::Literal {
+                        literal: Literal::Eot,
+                        matched: 1 + c.len_utf8(),
+                    },
+                    _ => return Err(error),
+                },
+            },
+            Stage::ComponentHeader(mut name) => match c {
+                ':' if self.declared.contains(&name) && !self.written.contains(&name) => {
+                    self.written.push(name.clone());
+                    self.current = Some(name);
+                    Stage::ComponentHeaderEnd
+                }
+                _ => {
+                    name.push(c);
+                    let possible = self.declared.iter().any(|declared| {
+                        declared.starts_with(&name) && !self.written.contains(declared)
+                    });
+                    if !possible {
+                        return Err(error);
+                    }
+                    Stage::ComponentHeader(name)
+                }
+            },
+            Stage::ComponentHeaderEnd => match c {
+                '\n' => {
+                    self.html = HtmlState::default();
+                    Stage::Html
+                }
+                _ => return Err(error),
+            },
+            Stage::Done => return Err(error),
+        };
+        Ok(())
+    }
+
+    fn push_html(&mut self, c: char) -> Result<HtmlStep, GrammarError> {
+        let error = GrammarError { found: c };
+        let node = match self.html.node {
+            Node::Text => match c {
+                '<' => Node::TagStart,
+                '\n' if self.html.open.is_empty() && self.html.has_content => {
+                    return Ok(HtmlStep::LineEnd)
+                }
+                '\n' => return Err(error),
+                _ => {
+                    self.html.has_content = true;
+                    Node::Text
+                }
+            },
+            Node::TagStart => match c {
+                // Every stop token starts with `<`
+                _ if self.html.open.is_empty()
+                    && self.html.has_content
+                    && self.template.stop_token()[1..].starts_with(c) =>
+                {
+                    return Ok(HtmlStep::Eot)
+                }
+                '/' if !self.html.open.is_empty() => {
+                    self.html.tag.clear();
+                    Node::CloseTag
+                }
+                // Icons are written as `<svg><!-- icon name --></svg>`
+                '!' if self.html.open.last().is_some_and(|tag| tag == "svg") => {
+                    Node::CommentStart(0)
+                }
+                _ if c.is_ascii_alphabetic() => {
+                    self.html.tag = c.to_string();
+                    if !self.is_tag_prefix(&self.html.tag) {
+                        return Err(error);
+                    }
+                    Node::TagName
+                }
+                _ => return Err(error),
+            },
+            Node::TagName => match c {
+                ' ' | '\t' | '>' | '/' => {
+                    if !self.is_tag(&self.html.tag) {
+                        return Err(error);
+                    }
+                    match c {
+                        '>' => self.open_tag(),
+                        '/' => Node::SelfClosing,
+                        _ => Node::InTag,
+                    }
+                }
+                _ if c.is_ascii_alphanumeric() || c == '_' => {
+                    self.html.tag.push(c);
+                    if !self.is_tag_prefix(&self.html.tag) {
+                        return Err(error);
+                    }
+                    Node::TagName
+                }
+                _ => return Err(error),
+            },
+            Node::InTag | Node::AfterAttribute => match c {
+                ' ' | '\t' => Node::InTag,
+                '>' => self.open_tag(),
+                '/' => Node::SelfClosing,
+                _ if self.html.node == Node::InTag
+                    && (c.is_ascii_alphabetic() || matches!(c, '_' | ':')) =>
+                {
+                    self.html.attribute = c.to_string();
+                    Node::AttributeName
+                }
+                _ => return Err(error),
+            },
+            Node::AttributeName => match c {
+                // Only children of a component can fill one of its slots
+                '=' if self.html.attribute == "slot" && !self.in_component() => return Err(error),
+                '=' => Node::AttributeQuote,
+                // Boolean attributes like `<input required>` can be written without a value
+                ' ' | '\t' | '>' | '/'
+                    if BOOLEAN_ATTRIBUTES.contains(&self.html.attribute.as_str()) =>
+                {
+                    match c {
+                        '>' => self.open_tag(),
+                        '/' => Node::SelfClosing,
+                        _ => Node::InTag,
+                    }
+                }
+                _ if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.') => {
+                    self.html.attribute.push(c);
+                    Node::AttributeName
+                }
+                _ => return Err(error),
+            },
+            Node::AttributeQuote => match c {
+                '"' => Node::AttributeValue,
+                _ => return Err(error),
+            },
+            Node::AttributeValue => match c {
+                '"' => Node::AfterAttribute,
+                '\n' => return Err(error),
+                _ => Node::AttributeValue,
+            },
+            Node::SelfClosing => match c {
+                '>' => {
+                    self.html.has_content = true;
+                    Node::Text
+                }
+                _ => return Err(error),
+            },
+            Node::CommentStart(dashes) => match c {
+                '-' if dashes == 1 => Node::Comment(0),
+                '-' => Node::CommentStart(1),
+                _ => return Err(error),
+            },
+            // Tracks the number of dashes before the end of the comment
+            Node::Comment(dashes) => match c {
+                '>' if dashes >= 2 => Node::Text,
+                '-' => Node::Comment(dashes.saturating_add(1)),
+                '\n' => return Err(error),
+                _ => Node::Comment(0),
+            },
+            Node::CloseTag => {
+                let Some(open) = self.html.open.last() else {
+                    return Err(error);
+                };
+                match c {
+                    '>' if *open == self.html.tag => {
+                        self.html.open.pop();
+                        Node::Text
+                    }
+                    _ => {
+                        self.html.tag.push(c);
+                        if !open.starts_with(&self.html.tag) {
+                            return Err(error);
+                        }
+                        Node::CloseTag
+                    }
+                }
+            }
+        };
+        self.html.node = node;
+        Ok(HtmlStep::Continue)
+    }
+
+    // Finish the opening tag. Void tags have no content or closing tag
+    fn open_tag(&mut self) -> Node {
+        self.html.has_content = true;
+        if !VOID_TAGS.contains(&self.html.tag.as_str()) {
+            self.html.open.push(self.html.tag.clone());
+        }
+        Node::Text
+    }
+
+    fn is_tag(&self, tag: &str) -> bool {
+        HTML_TAGS.contains(&tag) || self.can_render(tag)
+    }
+
+    fn is_tag_prefix(&self, prefix: &str) -> bool {
+        HTML_TAGS.iter().any(|tag| tag.starts_with(prefix))
+            || self
+                .declared
+                .iter()
+                .any(|component| component.starts_with(prefix) && self.can_render(component))
+    }
+
+    // If the innermost open tag is a component
+    fn in_component(&self) -> bool {
+        self.html
+            .open
+            .last()
+            .is_some_and(|tag| self.declared.contains(tag))
+    }
+
+    // Components can render any declared component except themselves
+    fn can_render(&self, component: &str) -> bool {
+        self.declared.iter().any(|declared| declared == component)
+            && self.current.as_deref() != Some(component)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct HtmlState {
+    node: Node,
+    /// The tags that are open, from the outermost to the innermost
+    open: Vec<String>,
+    /// The name of the tag that is being written
+    tag: String,
+    /// The name of the attribute that is being written
+    attribute: String,
+    has_content: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum Node {
+    #[default]
+    Text,
+    TagStart,
+    TagName,
+    InTag,
+    AttributeName,
+    AttributeQuote,
+    AttributeValue,
+    AfterAttribute,
+    SelfClosing,
+    CloseTag,
+    CommentStart(u8),
+    Comment(u8),
+}
+
+enum HtmlStep {
+    Continue,
+    /// The line of HTML ended with a newline
+    LineEnd,
+    /// The text after the HTML is `<|`
+    Eot,
+}
+
+// Component names start with an uppercase and a lowercase letter like `[A-Z][a-z]\w+`
+fn is_component_name_char(index: usize, c: char) -> bool {
+    match index {
+        0 => c.is_ascii_uppercase(),
+        1 => c.is_ascii_lowercase(),
+        _ => c.is_ascii_alphanumeric() || c == '_',
+    }
+}
+
+const HTML_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "blockquote",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "circle",
+    "code",
+    "col",
+    "colgroup",
+    "dd",
+    "del",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "g",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "line",
+    "main",
+    "mark",
+    "menu",
+    "meter",
+    "nav",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "path",
+    "picture",
+    "polygon",
+    "polyline",
+    "pre",
+    "progress",
+    "q",
+    "rect",
+    "s",
+    "section",
+    "select",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "svg",
+    "table",
+    "tbody",
+    "td",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "video",
+];
+
+const VOID_TAGS: &[&str] = &["br", "col", "hr", "img", "input", "source", "track"];
+
+#[test]
+fn html_grammar() {
+    let ui = "A profile card.
+components:
+- Avatar: A round user avatar
+- Badge: A small label
+interactive:
+- Badge: is_open, count
+HTML:
+<div class=\"p-4\"><Avatar/><h2>{name}</h2><Badge></Badge></div>
+component html:
+Badge:
+<span class=\"rounded\">{label}</span>
+Avatar:
+<img class=\"rounded-full\" src=\"{avatar_url}\"><Badge/><|eot_id|>";
+    assert!(OutputGrammar::ui().accepts(ui));
+    // Every declared component needs HTML
+    assert!(!OutputGrammar::ui().accepts(&ui.replace(
+        "\nAvatar:\n<img class=\"rounded-full\" src=\"{avatar_url}\"><Badge/>",
+        ""
+    )));
+    assert!(OutputGrammar::sections().accepts(&format!(
+        "{}\n",
+        ui.split_once("\ncomponent html:").unwrap().0
+    )));
+
+    let html = |html: &str| {
+        OutputGrammar::component_html(["Card", "Avatar"], "Card").accepts(&format!("{html}\n"))
+    };
+    assert!(html("<div class=\"flex\"><Avatar/>{children}</div>"));
+    assert!(html("<ul><li>One</li><li>Two</li></ul><br>"));
+    assert!(html(
+        "<button><svg class=\"w-4\"><!-- arrow-right --></svg></button>"
+    ));
+    assert!(!html("<div><!-- note --></div>"));
+    assert!(html("<Avatar><span slot=\"badge\">New</span></Avatar>"));
+    assert!(!html("<div><span slot=\"badge\">New</span></div>"));
+    assert!(!html("<svg><!-- arrow-right -></svg>"));
+    assert!(!html("<div><span></div></span>"));
+    assert!(!html("<div>"));
+    assert!(!html("<blink>hi</blink>"));
+    assert!(!html("<div class=flex></div>"));
+    assert!(html(
+        "<form><input required class=\"border\"><button disabled>Send</button></form>"
+    ));
+    assert!(html("<input type=\"checkbox\" checked/>"));
+    assert!(!html("<div class></div>"));
+    assert!(!html("<input required=true>"));
+    assert!(!html("<Header/>"));
+    assert!(!html("<Card/>"));
+    assert!(!html("<img src=\"a.png\"></img>"));
+
+    let mut state = OutputGrammar::ui().state();
+    state.push_str("A page.\ncompo").unwrap();
+    assert_eq!(state.required_next(), "nents:\n");
+
+    assert!(OutputGrammar::pages().accepts("- Home: The landing page\n- Pricing: The plans\n\n"));
+    assert!(!OutputGrammar::pages().accepts("\n"));
+    assert!(!OutputGrammar::pages().accepts("- Home: The landing page\nHTML:\n"));
+
+    // The response ends with the stop token of the chat template
+    let mistral = OutputGrammar::ui().with_chat_template(ChatTemplate::Mistral);
+    assert!(mistral.accepts(&ui.replace("<|eot_id|>", "</s>")));
+    assert!(!mistral.accepts(ui));
+    let mut state = OutputGrammar::ui()
+        .with_chat_template(ChatTemplate::Phi3)
+        .state();
+    state.push_str(&ui.replace("<|eot_id|>", "<|e")).unwrap();
+    assert_eq!(state.required_next(), "nd|>");
+
+    // The HTML of a single component can end the response
+    let card =
+        OutputGrammar::component_html(["Card"], "Card").with_chat_template(ChatTemplate::Mistral);
+    assert!(card.accepts("<p><s>$99</s> $49</p></s>"));
+    assert!(card.accepts("<p><s>$99</s> $49</p>\n"));
+    assert!(!card.accepts("<p>$49</p><|eot_id|>"));
+}
@@ -0,0 +1,470 @@
+use regex::{Captures, Regex};
+
+// The model writes icons as `<svg class="w-6 h-6"><!-- download --></svg>`
+const ICON_PLACEHOLDER: &str = r"<svg([^>]*)>\s*<!--\s*(.*?)\s*-->\s*</svg>";
+
+// The attributes every icon needs to render like the icon set. Attributes the model already wrote are kept
+const ICON_ATTRIBUTES: &[(&str, &str)] = &[
+    ("xmlns", "http://www.w3.org/2000/svg"),
+    ("viewBox", "0 0 24 24"),
+    ("fill", "none"),
+    ("stroke", "currentColor"),
+    ("stroke-width", "2"),
+    ("stroke-linecap", "round"),
+    ("stroke-linejoin", "round"),
+];
+
+/// Replace every `<svg><!-- icon name --></svg>` placeholder in the HTML with the paths of the closest icon in the embedded icon set. Returns the new HTML and the names of any icons that could not be found. Unknown icons are left as empty svg elements.
+pub(crate) fn resolve_icons(html: &str) -> (String, Vec<String>) {
+    let placeholder_regex = Regex::new(ICON_PLACEHOLDER).unwrap();
+    let attribute_regex = Regex::new(r#"([\w-]+)\s*="#).unwrap();
+    let mut unknown = Vec::new();
+    let html = placeholder_regex.replace_all(html, |captures: &Captures| {
+        let attributes = &captures[1];
+        let name = &captures[2];
+        let Some(paths) = find_icon(name) else {
+            unknown.push(name.to_string());
+            return format!("<svg{attributes}></svg>");
+        };
+        let existing = attribute_regex
+            .captures_iter(attributes)
+            .map(|attribute| attribute[1].to_lowercase())
+            .collect::<Vec<_>>();
+        let mut svg = format!("<svg{}", attributes.trim_end().trim_end_matches('/'));
+        for (attribute, value) in ICON_ATTRIBUTES {
+            if !existing.contains(&attribute.to_lowercase()) {
+                svg += &format!(" {attribute}=\"{value}\"");
+            }
+        }
+        // Without any styles the icon would fill the whole container
+        if !existing
+            .iter()
+            .any(|attribute| ["class", "width", "height"].contains(&attribute.as_str()))
+        {
+            svg += " width=\"24\" height=\"24\"";
+        }
+        format!("{svg}>{paths}</svg>")
+    });
+    (html.to_string(), unknown)
+}
+
+/// Find the paths of an icon by name. Names are matched exactly, then by alias, then by any of the words in the name, and finally by a single spelling mistake.
+pub(crate) fn find_icon(name: &str) -> Option<&'static str> {
+    let name = normalize(name);
+    if name.is_empty() {
+        return None;
+    }
+    if let Some(paths) = exact_icon(&name) {
+        return Some(paths);
+    }
+
+    // "user-profile-avatar" matches "user", the longest run of words that is an icon. Icon names are preferred over aliases, so "open-menu" is "menu"
+    let words = name.split('-').collect::<Vec<_>>();
+    let runs = (1..words.len())
+        .rev()
+        .flat_map(|length| (0..=words.len() - length).map(move |start| (start, length)))
+        .map(|(start, length)| words[start..start + length].join("-"))
+        .collect::<Vec<_>>();
+    if let Some(paths) = runs.iter().find_map(|run| icon(run)) {
+        return Some(paths);
+    }
+    if let Some(paths) = runs.iter().find_map(|run| exact_icon(run)) {
+        return Some(paths);
+    }
+
+    // Fix a small spelling mistake like "calender". Short names are skipped, because "cat" is one letter from "cart"
+    if name.len() < 5 {
+        return None;
+    }
+    ICONS
+        .iter()
+        .filter(|(icon, _)| edit_distance(&name, icon) <= 1)
+        .map(|(_, paths)| *paths)
+        .next()
+}
+
+fn exact_icon(name: &str) -> Option<&'static str> {
+    let name = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map(|(_, icon)| *icon)
+        .unwrap_or(name);
+    icon(name)
+}
+
+fn icon(name: &str) -> Option<&'static str> {
+    ICONS
+        .iter()
+        .find(|(icon, _)| *icon == name)
+        .map(|(_, paths)| *paths)
+}
+
+// Lowercase kebab case without an "icon" prefix or suffix
+fn normalize(name: &str) -> String {
+    let words = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>();
+    let words = match words.as_slice() {
+        [first, rest @ ..] if first == "icon" && !rest.is_empty() => rest,
+        [rest @ .., last] if last == "icon" && !rest.is_empty() => rest,
+        words => words,
+    };
+    words.join("-")
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+const ALIASES: &[(&str, &str)] = &[
+    ("close", "x"),
+    ("cross", "x"),
+    ("cancel", "x"),
+    ("hamburger", "menu"),
+    ("bars", "menu"),
+    ("magnifying-glass", "search"),
+    ("magnifier", "search"),
+    ("tick", "check"),
+    ("checkmark", "check"),
+    ("add", "plus"),
+    ("remove", "minus"),
+    ("delete", "trash"),
+    ("bin", "trash"),
+    ("pencil", "edit"),
+    ("pen", "edit"),
+    ("cart", "shopping-cart"),
+    ("basket", "shopping-cart"),
+    ("profile", "user"),
+    ("person", "user"),
+    ("account", "user"),
+    ("avatar", "user"),
+    ("people", "users"),
+    ("team", "users"),
+    ("house", "home"),
+    ("email", "mail"),
+    ("envelope", "mail"),
+    ("notification", "bell"),
+    ("notifications", "bell"),
+    ("like", "heart"),
+    ("love", "heart"),
+    ("favorite", "star"),
+    ("rating", "star"),
+    ("gear", "settings"),
+    ("cog", "settings"),
+    ("date", "calendar"),
+    ("time", "clock"),
+    ("password", "lock"),
+    ("secure", "lock"),
+    ("view", "eye"),
+    ("visible", "eye"),
+    ("location", "map-pin"),
+    ("pin", "map-pin"),
+    ("logout", "log-out"),
+    ("sign-out", "log-out"),
+    ("chat", "message-square"),
+    ("comment", "message-square"),
+    ("message", "message-square"),
+    ("light", "sun"),
+    ("dark", "moon"),
+    ("photo", "image"),
+    ("picture", "image"),
+    ("document", "file"),
+    ("url", "link"),
+    ("world", "globe"),
+    ("website", "globe"),
+    ("language", "globe"),
+    ("save", "bookmark"),
+    ("more", "more-horizontal"),
+    ("dots", "more-horizontal"),
+    ("ellipsis", "more-horizontal"),
+    ("reload", "refresh-cw"),
+    ("refresh", "refresh-cw"),
+    ("payment", "credit-card"),
+    ("card", "credit-card"),
+    ("warning", "alert-circle"),
+    ("error", "alert-circle"),
+    ("alert", "alert-circle"),
+    ("help", "info"),
+    ("next", "chevron-right"),
+    ("previous", "chevron-left"),
+    ("back", "arrow-left"),
+    ("forward", "arrow-right"),
+    ("expand", "chevron-down"),
+    ("dropdown", "chevron-down"),
+    ("collapse", "chevron-up"),
+    ("chevron", "chevron-down"),
+    ("caret", "chevron-down"),
+    ("external", "external-link"),
+    ("telephone", "phone"),
+    ("call", "phone"),
+    ("mobile", "phone"),
+    ("git-hub", "github"),
+    ("label", "tag"),
+    ("price", "tag"),
+    ("success", "check-circle"),
+    ("done", "check-circle"),
+    ("failure", "x-circle"),
+];
+
+// Icons from Feather (https://feathericons.com), MIT license
+const ICONS: &[(&str, &str)] = &[
+    (
+        "search",
+        r#"<circle cx="11" cy="11" r="8"/><line x1="21" y1="21" x2="16.65" y2="16.65"/>"#,
+    ),
+    (
+        "menu",
+        r#"<line x1="3" y1="12" x2="21" y2="12"/><line x1="3" y1="6" x2="21" y2="6"/><line x1="3" y1="18" x2="21" y2="18"/>"#,
+    ),
+    (
+        "x",
+        r#"<line x1="18" y1="6" x2="6" y2="18"/><line x1="6" y1="6" x2="18" y2="18"/>"#,
+    ),
+    ("check", r#"<polyline points="20 6 9 17 4 12"/>"#),
+    (
+        "check-circle",
+        r#"<path d="M22 11.08V12a10 10 0 1 1-5.93-9.14"/><polyline points="22 4 12 14.01 9 11.01"/>"#,
+    ),
+    (
+        "x-circle",
+        r#"<circle cx="12" cy="12" r="10"/><line x1="15" y1="9" x2="9" y2="15"/><line x1="9" y1="9" x2="15" y2="15"/>"#,
+    ),
+    (
+        "plus",
+        r#"<line x1="12" y1="5" x2="12" y2="19"/><line x1="5" y1="12" x2="19" y2="12"/>"#,
+    ),
+    ("minus", r#"<line x1="5" y1="12" x2="19" y2="12"/>"#),
+    ("chevron-down", r#"<polyline points="6 9 12 15 18 9"/>"#),
+    ("chevron-up", r#"<polyline points="18 15 12 9 6 15"/>"#),
+    ("chevron-left", r#"<polyline points="15 18 9 12 15 6"/>"#),
+    ("chevron-right", r#"<polyline points="9 18 15 12 9 6"/>"#),
+    (
+        "arrow-right",
+        r#"<line x1="5" y1="12" x2="19" y2="12"/><polyline points="12 5 19 12 12 19"/>"#,
+    ),
+    (
+        "arrow-left",
+        r#"<line x1="19" y1="12" x2="5" y2="12"/><polyline points="12 19 5 12 12 5"/>"#,
+    ),
+    (
+        "arrow-up",
+        r#"<line x1="12" y1="19" x2="12" y2="5"/><polyline points="5 12 12 5 19 12"/>"#,
+    ),
+    (
+        "arrow-down",
+        r#"<line x1="12" y1="5" x2="12" y2="19"/><polyline points="19 12 12 19 5 12"/>"#,
+    ),
+    (
+        "download",
+        r#"<path d="M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4"/><polyline points="7 10 12 15 17 10"/><line x1="12" y1="15" x2="12" y2="3"/>"#,
+    ),
+    (
+        "upload",
+        r#"<path d="M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4"/><polyline points="17 8 12 3 7 8"/><line x1="12" y1="3" x2="12" y2="15"/>"#,
+    ),
+    (
+        "user",
+        r#"<path d="M20 21v-2a4 4 0 0 0-4-4H8a4 4 0 0 0-4 4v2"/><circle cx="12" cy="7" r="4"/>"#,
+    ),
+    (
+        "users",
+        r#"<path d="M17 21v-2a4 4 0 0 0-4-4H5a4 4 0 0 0-4 4v2"/><circle cx="9" cy="7" r="4"/><path d="M23 21v-2a4 4 0 0 0-3-3.87"/><path d="M16 3.13a4 4 0 0 1 0 7.75"/>"#,
+    ),
+    (
+        "home",
+        r#"<path d="M3 9l9-7 9 7v11a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2z"/><polyline points="9 22 9 12 15 12 15 22"/>"#,
+    ),
+    (
+        "mail",
+        r#"<path d="M4 4h16c1.1 0 2 .9 2 2v12c0 1.1-.9 2-2 2H4c-1.1 0-2-.9-2-2V6c0-1.1.9-2 2-2z"/><polyline points="22,6 12,13 2,6"/>"#,
+    ),
+    (
+        "heart",
+        r#"<path d="M20.84 4.61a5.5 5.5 0 0 0-7.78 0L12 5.67l-1.06-1.06a5.5 5.5 0 0 0-7.78 7.78l1.06 1.06L12 21.23l7.78-7.78 1.06-1.06a5.5 5.5 0 0 0 0-7.78z"/>"#,
+    ),
+    (
+        "star",
+        r#"<polygon points="12 2 15.09 8.26 22 9.27 17 14.14 18.18 21.02 12 17.77 5.82 21.02 7 14.14 2 9.27 8.91 8.26 12 2"/>"#,
+    ),
+    (
+        "bell",
+        r#"<path d="M18 8A6 6 0 0 0 6 8c0 7-3 9-3 9h18s-3-2-3-9"/><path d="M13.73 21a2 2 0 0 1-3.46 0"/>"#,
+    ),
+    (
+        "settings",
+        r#"<circle cx="12" cy="12" r="3"/><path d="M19.4 15a1.65 1.65 0 0 0 .33 1.82l.06.06a2 2 0 0 1 0 2.83 2 2 0 0 1-2.83 0l-.06-.06a1.65 1.65 0 0 0-1.82-.33 1.65 1.65 0 0 0-1 1.51V21a2 2 0 0 1-2 2 2 2 0 0 1-2-2v-.09A1.65 1.65 0 0 0 9 19.4a1.65 1.65 0 0 0-1.82.33l-.06.06a2 2 0 0 1-2.83 0 2 2 0 0 1 0-2.83l.06-.06a1.65 1.65 0 0 0 .33-1.82 1.65 1.65 0 0 0-1.51-1H3a2 2 0 0 1-2-2 2 2 0 0 1 2-2h.09A1.65 1.65 0 0 0 4.6 9a1.65 1.65 0 0 0-.33-1.82l-.06-.06a2 2 0 0 1 0-2.83 2 2 0 0 1 2.83 0l.06.06a1.65 1.65 0 0 0 1.82.33H9a1.65 1.65 0 0 0 1-1.51V3a2 2 0 0 1 2-2 2 2 0 0 1 2 2v.09a1.65 1.65 0 0 0 1 1.51 1.65 1.65 0 0 0 1.82-.33l.06-.06a2 2 0 0 1 2.83 0 2 2 0 0 1 0 2.83l-.06.06a1.65 1.65 0 0 0-.33 1.82V9a1.65 1.65 0 0 0 1.51 1H21a2 2 0 0 1 2 2 2 2 0 0 1-2 2h-.09a1.65 1.65 0 0 0-1.51 1z"/>"#,
+    ),
+    (
+        "trash",
+        r#"<polyline points="3 6 5 6 21 6"/><path d="M19 6v14a2 2 0 0 1-2 2H7a2 2 0 0 1-2-2V6m3 0V4a2 2 0 0 1 2-2h4a2 2 0 0 1 2 2v2"/>"#,
+    ),
+    (
+        "edit",
+        r#"<path d="M11 4H4a2 2 0 0 0-2 2v14a2 2 0 0 0 2 2h14a2 2 0 0 0 2-2v-7"/><path d="M18.5 2.5a2.121 2.121 0 0 1 3 3L12 15l-4 1 1-4 9.5-9.5z"/>"#,
+    ),
+    (
+        "shopping-cart",
+        r#"<circle cx="9" cy="21" r="1"/><circle cx="20" cy="21" r="1"/><path d="M1 1h4l2.68 13.39a2 2 0 0 0 2 1.61h9.72a2 2 0 0 0 2-1.61L23 6H6"/>"#,
+    ),
+    (
+        "calendar",
+        r#"<rect x="3" y="4" width="18" height="18" rx="2" ry="2"/><line x1="16" y1="2" x2="16" y2="6"/><line x1="8" y1="2" x2="8" y2="6"/><line x1="3" y1="10" x2="21" y2="10"/>"#,
+    ),
+    (
+        "clock",
+        r#"<circle cx="12" cy="12" r="10"/><polyline points="12 6 12 12 16 14"/>"#,
+    ),
+    (
+        "lock",
+        r#"<rect x="3" y="11" width="18" height="11" rx="2" ry="2"/><path d="M7 11V7a5 5 0 0 1 10 0v4"/>"#,
+    ),
+    (
+        "eye",
+        r#"<path d="M1 12s4-8 11-8 11 8 11 8-4 8-11 8-11-8-11-8z"/><circle cx="12" cy="12" r="3"/>"#,
+    ),
+    ("play", r#"<polygon points="5 3 19 12 5 21 5 3"/>"#),
+    (
+        "pause",
+        r#"<rect x="6" y="4" width="4" height="16"/><rect x="14" y="4" width="4" height="16"/>"#,
+    ),
+    (
+        "share",
+        r#"<path d="M4 12v8a2 2 0 0 0 2 2h12a2 2 0 0 0 2-2v-8"/><polyline points="16 6 12 2 8 6"/><line x1="12" y1="2" x2="12" y2="15"/>"#,
+    ),
+    (
+        "external-link",
+        r#"<path d="M18 13v6a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2V8a2 2 0 0 1 2-2h6"/><polyline points="15 3 21 3 21 9"/><line x1="10" y1="14" x2="21" y2="3"/>"#,
+    ),
+    (
+        "info",
+        r#"<circle cx="12" cy="12" r="10"/><line x1="12" y1="16" x2="12" y2="12"/><line x1="12" y1="8" x2="12.01" y2="8"/>"#,
+    ),
+    (
+        "alert-circle",
+        r#"<circle cx="12" cy="12" r="10"/><line x1="12" y1="8" x2="12" y2="12"/><line x1="12" y1="16" x2="12.01" y2="16"/>"#,
+    ),
+    (
+        "filter",
+        r#"<polygon points="22 3 2 3 10 12.46 10 19 14 21 14 12.46 22 3"/>"#,
+    ),
+    (
+        "map-pin",
+        r#"<path d="M21 10c0 7-9 13-9 13s-9-6-9-13a9 9 0 0 1 18 0z"/><circle cx="12" cy="10" r="3"/>"#,
+    ),
+    (
+        "log-out",
+        r#"<path d="M9 21H5a2 2 0 0 1-2-2V5a2 2 0 0 1 2-2h4"/><polyline points="16 17 21 12 16 7"/><line x1="21" y1="12" x2="9" y2="12"/>"#,
+    ),
+    (
+        "message-square",
+        r#"<path d="M21 15a2 2 0 0 1-2 2H7l-4 4V5a2 2 0 0 1 2-2h14a2 2 0 0 1 2 2z"/>"#,
+    ),
+    (
+        "sun",
+        r#"<circle cx="12" cy="12" r="5"/><line x1="12" y1="1" x2="12" y2="3"/><line x1="12" y1="21" x2="12" y2="23"/><line x1="4.22" y1="4.22" x2="5.64" y2="5.64"/><line x1="18.36" y1="18.36" x2="19.78" y2="19.78"/><line x1="1" y1="12" x2="3" y2="12"/><line x1="21" y1="12" x2="23" y2="12"/><line x1="4.22" y1="19.78" x2="5.64" y2="18.36"/><line x1="18.36" y1="5.64" x2="19.78" y2="4.22"/>"#,
+    ),
+    (
+        "moon",
+        r#"<path d="M21 12.79A9 9 0 1 1 11.21 3 7 7 0 0 0 21 12.79z"/>"#,
+    ),
+    (
+        "image",
+        r#"<rect x="3" y="3" width="18" height="18" rx="2" ry="2"/><circle cx="8.5" cy="8.5" r="1.5"/><polyline points="21 15 16 10 5 21"/>"#,
+    ),
+    (
+        "file",
+        r#"<path d="M13 2H6a2 2 0 0 0-2 2v16a2 2 0 0 0 2 2h12a2 2 0 0 0 2-2V9z"/><polyline points="13 2 13 9 20 9"/>"#,
+    ),
+    (
+        "link",
+        r#"<path d="M10 13a5 5 0 0 0 7.54.54l3-3a5 5 0 0 0-7.07-7.07l-1.72 1.71"/><path d="M14 11a5 5 0 0 0-7.54-.54l-3 3a5 5 0 0 0 7.07 7.07l1.71-1.71"/>"#,
+    ),
+    (
+        "globe",
+        r#"<circle cx="12" cy="12" r="10"/><line x1="2" y1="12" x2="22" y2="12"/><path d="M12 2a15.3 15.3 0 0 1 4 10 15.3 15.3 0 0 1-4 10 15.3 15.3 0 0 1-4-10 15.3 15.3 0 0 1 4-10z"/>"#,
+    ),
+    (
+        "bookmark",
+        r#"<path d="M19 21l-7-5-7 5V5a2 2 0 0 1 2-2h10a2 2 0 0 1 2 2z"/>"#,
+    ),
+    (
+        "more-horizontal",
+        r#"<circle cx="12" cy="12" r="1"/><circle cx="19" cy="12" r="1"/><circle cx="5" cy="12" r="1"/>"#,
+    ),
+    (
+        "refresh-cw",
+        r#"<polyline points="23 4 23 10 17 10"/><polyline points="1 20 1 14 7 14"/><path d="M3.51 9a9 9 0 0 1 14.85-3.36L23 10M1 14l4.64 4.36A9 9 0 0 0 20.49 15"/>"#,
+    ),
+    (
+        "send",
+        r#"<line x1="22" y1="2" x2="11" y2="13"/><polygon points="22 2 15 22 11 13 2 9 22 2"/>"#,
+    ),
+    (
+        "credit-card",
+        r#"<rect x="1" y="4" width="22" height="16" rx="2" ry="2"/><line x1="1" y1="10" x2="23" y2="10"/>"#,
+    ),
+    (
+        "phone",
+        r#"<path d="M22 16.92v3a2 2 0 0 1-2.18 2 19.79 19.79 0 0 1-8.63-3.07 19.5 19.5 0 0 1-6-6 19.79 19.79 0 0 1-3.07-8.67A2 2 0 0 1 4.11 2h3a2 2 0 0 1 2 1.72 12.84 12.84 0 0 0 .7 2.81 2 2 0 0 1-.45 2.11L8.09 9.91a16 16 0 0 0 6 6l1.27-1.27a2 2 0 0 1 2.11-.45 12.84 12.84 0 0 0 2.81.7A2 2 0 0 1 22 16.92z"/>"#,
+    ),
+    (
+        "camera",
+        r#"<path d="M23 19a2 2 0 0 1-2 2H3a2 2 0 0 1-2-2V8a2 2 0 0 1 2-2h4l2-3h6l2 3h4a2 2 0 0 1 2 2z"/><circle cx="12" cy="13" r="4"/>"#,
+    ),
+    (
+        "tag",
+        r#"<path d="M20.59 13.41l-7.17 7.17a2 2 0 0 1-2.83 0L2 12V2h10l8.59 8.59a2 2 0 0 1 0 2.82z"/><line x1="7" y1="7" x2="7.01" y2="7"/>"#,
+    ),
+    (
+        "github",
+        r#"<path d="M9 19c-5 1.5-5-2.5-7-3m14 6v-3.87a3.37 3.37 0 0 0-.94-2.61c3.14-.35 6.44-1.54 6.44-7A5.44 5.44 0 0 0 20 4.77 5.07 5.07 0 0 0 19.91 1S18.73.65 16 2.48a13.38 13.38 0 0 0-7 0C6.27.65 5.09 1 5.09 1A5.07 5.07 0 0 0 5 4.77a5.44 5.44 0 0 0-1.5 3.78c0 5.42 3.3 6.61 6.44 7A3.37 3.37 0 0 0 9 18.13V22"/>"#,
+    ),
+    (
+        "grid",
+        r#"<rect x="3" y="3" width="7" height="7"/><rect x="14" y="3" width="7" height="7"/><rect x="14" y="14" width="7" height="7"/><rect x="3" y="14" width="7" height="7"/>"#,
+    ),
+    (
+        "list",
+        r#"<line x1="8" y1="6" x2="21" y2="6"/><line x1="8" y1="12" x2="21" y2="12"/><line x1="8" y1="18" x2="21" y2="18"/><line x1="3" y1="6" x2="3.01" y2="6"/><line x1="3" y1="12" x2="3.01" y2="12"/><line x1="3" y1="18" x2="3.01" y2="18"/>"#,
+    ),
+];
+
+#[test]
+fn resolve_icon_placeholders() {
+    assert_eq!(find_icon("download"), find_icon("Download Icon"));
+    assert_eq!(find_icon("close"), find_icon("x"));
+    assert_eq!(find_icon("user-profile-avatar"), find_icon("user"));
+    assert_eq!(find_icon("calender"), find_icon("calendar"));
+    assert_eq!(find_icon("spaceship"), None);
+
+    // Short names and names that contain another icon are not misspellings
+    assert_eq!(find_icon("cat"), None);
+    assert_eq!(find_icon("open-menu"), find_icon("menu"));
+    assert_ne!(find_icon("x-circle"), find_icon("x"));
+    assert_ne!(find_icon("check-circle"), find_icon("check"));
+    for name in ["github", "phone", "camera", "tag", "chevron"] {
+        assert!(find_icon(name).is_some(), "{name}");
+    }
+
+    let (html, unknown) = resolve_icons(
+        r#"<button><svg class="w-6 h-6"><!-- download --></svg><svg><!--spaceship--></svg></button>"#,
+    );
+    assert_eq!(unknown, ["spaceship"]);
+    assert!(html.starts_with(r#"<button><svg class="w-6 h-6" xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none""#));
+    assert!(html.contains(
+        r#"<polyline points="7 10 12 15 17 10"/><line x1="12" y1="15" x2="12" y2="3"/></svg>"#
+    ));
+    assert!(html.ends_with("<svg></svg></button>"));
+}
@@ -0,0 +1,412 @@
+use crate::naming::{prop_name, rust_ident};
+use crate::props::type_from_name;
+use crate::{DioxusVersion, Prop, PropType};
+use regex::Regex;
+
+// State that is stored in a signal inside the component
+struct Signal {
+    name: String,
+    ty: PropType,
+    // If any event handler sets the signal
+    mutable: bool,
+}
+
+impl Signal {
+    fn hook(&self, target: DioxusVersion) -> String {
+        let initial = match self.ty {
+            PropType::Integer => "|| 0",
+            PropType::Float => "|| 0.0",
+            PropType::Bool => "|| false",
+            _ => "String::new",
+        };
+        target.state_hook(&rust_ident(&self.name), initial, self.mutable)
+    }
+}
+
+/// Add `use_signal` state and event handlers to the interactive elements in the rsx of a component. Returns the new rsx and the hooks that need to run before it.
+///
+/// Text inputs, text areas and selects get a string signal bound to their value, checkboxes get a bool signal bound to `checked`, and buttons and forms call an `EventHandler` prop. `state` is the state the model declared for the component in the interactive section. Placeholders with those names become signals instead of props. Buttons toggle the first bool state, or select the index of the button in the first integer state.
+pub(crate) fn add_interactivity(
+    rsx: &str,
+    props: &mut Vec<Prop>,
+    state: &[String],
+    target: DioxusVersion,
+) -> (String, Vec<String>) {
+    let value = target.event_value();
+    let mut signals = Vec::new();
+    for name in state {
+        let ty = match props.iter().position(|prop| prop.name == *name) {
+            Some(index) => props.remove(index).ty,
+            None => type_from_name(name),
+        };
+        // State like `selected_tab` is the index of the selected button
+        let ty = match ty {
+            PropType::String if is_index_name(name) => PropType::Integer,
+            ty => ty,
+        };
+        if ty != PropType::Element {
+            signals.push(Signal {
+                name: name.clone(),
+                ty,
+                mutable: false,
+            });
+        }
+    }
+    let button_state = signals
+        .iter()
+        .position(|signal| signal.ty == PropType::Bool)
+        .or_else(|| {
+            signals
+                .iter()
+                .position(|signal| signal.ty == PropType::Integer)
+        });
+
+    let element_regex = Regex::new(r"\b(input|textarea|select|button|form)\s*\{").unwrap();
+    let placeholder_regex = Regex::new(r"\{[^}]*\}").unwrap();
+    let strings = string_mask(rsx);
+    let mut output = String::new();
+    let mut copied = 0;
+    let mut buttons = 0;
+    for element in element_regex.captures_iter(rsx) {
+        let whole = element.get(0).unwrap();
+        if strings[whole.start()] {
+            continue;
+        }
+        let tag = element.get(1).unwrap().as_str();
+        let body = &rsx[whole.end()..block_end(rsx, whole.end() - 1)];
+        let (attributes, text) = top_level_attributes(body);
+        let attribute = |name: &str| {
+            attributes
+                .iter()
+                .find(|(attribute, _)| attribute == name)
+                .map(|(_, value)| value.as_str())
+        };
+        let input_type = attribute("type").unwrap_or("text");
+
+        let mut insert = Vec::new();
+        match tag {
+            "input"
+                if matches!(
+                    input_type,
+                    "submit" | "button" | "reset" | "radio" | "hidden"
+                ) => {}
+            "input" | "textarea" | "select" if attribute("oninput").is_some() => {}
+            "input" if input_type == "checkbox" && attribute("checked").is_some() => {}
+            "input" if input_type == "checkbox" => {
+                let name = unique_name(
+                    attribute("name").or(attribute("id")).unwrap_or("checked"),
+                    props,
+                    &signals,
+                );
+                insert.push(format!("checked: {}", target.read(&name)));
+                insert.push(format!(
+                    "oninput: move |event| {name}.set({value} == \"true\")"
+                ));
+                signals.push(Signal {
+                    name,
+                    ty: PropType::Bool,
+                    mutable: true,
+                });
+            }
+            "input" | "textarea" | "select" => {
+                if let Some(value) = attribute("value") {
+                    // Keep values that are already bound to declared state in sync
+                    let bound = value
+                        .strip_prefix('{')
+                        .and_then(|value| value.strip_suffix('}'))
+                        .and_then(|name| {
+                            signals
+                                .iter_mut()
+                                .find(|signal| signal.name == name && signal.ty == PropType::String)
+                        });
+                    if let Some(signal) = bound {
+                        signal.mutable = true;
+                        insert.push(format!(
+                            "oninput: move |event| {}.set({value})",
+                            signal.name
+                        ));
+                    }
+                } else {
+                    let fallback = match tag {
+                        "input" => input_type,
+                        _ => tag,
+                    };
+                    let name = unique_name(
+                        attribute("name").or(attribute("id")).unwrap_or(fallback),
+                        props,
+                        &signals,
+                    );
+                    insert.push(format!("value: \"{{{name}}}\""));
+                    insert.push(format!("oninput: move |event| {name}.set({value})"));
+                    signals.push(Signal {
+                        name,
+                        ty: PropType::String,
+                        mutable: true,
+                    });
+                }
+            }
+            "button" if input_type == "submit" || attribute("onclick").is_some() => {}
+            "button" => {
+                match button_state.map(|index| &mut signals[index]) {
+                    Some(signal) => {
+                        signal.mutable = true;
+                        if signal.ty == PropType::Bool {
+                            insert
+                                .push(format!("onclick: move |_| {}", target.toggle(&signal.name)));
+                        } else {
+                            insert
+                                .push(format!("onclick: move |_| {}.set({buttons})", signal.name));
+                        }
+                    }
+                    None => {
+                        // Name the handler after the static text of the button
+                        let text = text
+                            .iter()
+                            .map(|text| placeholder_regex.replace_all(text, " "))
+                            .collect::<Vec<_>>();
+                        let words = text
+                            .iter()
+                            .flat_map(|text| text.split(|c: char| !c.is_ascii_alphabetic()))
+                            .filter(|word| !word.is_empty())
+                            .take(3)
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        let words = if words.is_empty() { "click" } else { &words };
+                        let name = unique_name(&format!("on {words}"), props, &signals);
+                        insert.push(format!("onclick: move |event| {name}.call(event)"));
+                        props.push(Prop {
+                            name,
+                            ty: PropType::MouseEventHandler,
+                            optional: true,
+                        });
+                    }
+                }
+                buttons += 1;
+            }
+            "form" if attribute("onsubmit").is_none() => {
+                let name = unique_name("on_submit", props, &signals);
+                insert.push("prevent_default: \"onsubmit\"".to_string());
+                insert.push(format!("onsubmit: move |event| {name}.call(event)"));
+                props.push(Prop {
+                    name,
+                    ty: PropType::FormEventHandler,
+                    optional: true,
+                });
+            }
+            _ => {}
+        }
+
+        if !insert.is_empty() {
+            output += &rsx[copied..whole.end()];
+            output += &format!(" {},", insert.join(", "));
+            copied = whole.end();
+        }
+    }
+    output += &rsx[copied..];
+
+    // Typed state that was the whole value of an attribute is read from the signal
+    for signal in &signals {
+        if signal.ty == PropType::String {
+            continue;
+        }
+        let attribute_regex = Regex::new(&format!(
+            r#"(["\w-]+\s*:\s*)"\{{{}\}}""#,
+            regex::escape(&signal.name)
+        ))
+        .unwrap();
+        output = attribute_regex
+            .replace_all(&output, format!("${{1}}{}", target.read(&signal.name)))
+            .to_string();
+    }
+
+    let hooks = signals.iter().map(|signal| signal.hook(target)).collect();
+    (output, hooks)
+}
+
+// State that stores the index of a button, like `active_tab` or `current_page`
+pub(crate) fn is_index_name(name: &str) -> bool {
+    let last = name.rsplit('_').next().unwrap_or_default();
+    matches!(last, "index" | "tab" | "page" | "step" | "slide")
+}
+
+// A snake case name that does not collide with a prop or another signal. Attribute values like `user.email`, `tags[]` or `{field}` are turned into identifiers first
+fn unique_name(name: &str, props: &[Prop], signals: &[Signal]) -> String {
+    let base = prop_name(name);
+    let taken = |name: &str| {
+        props.iter().any(|prop| prop.name == name)
+            || signals.iter().any(|signal| signal.name == name)
+    };
+    let mut name = base.clone();
+    let mut index = 2;
+    while taken(&name) {
+        name = format!("{base}_{index}");
+        index += 1;
+    }
+    name
+}
+
+// For each byte of the rsx, whether it is inside a string literal
+pub(crate) fn string_mask(rsx: &str) -> Vec<bool> {
+    let mut mask = Vec::with_capacity(rsx.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for byte in rsx.bytes() {
+        let starts_string = !in_string && byte == b'"';
+        mask.push(in_string || starts_string);
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if byte == b'\\' {
+                escaped = true;
+            } else if byte == b'"' {
+                in_string = false;
+            }
+        } else if starts_string {
+            in_string = true;
+        }
+    }
+    mask
+}
+
+// The index of the brace that closes the block opened at `open`
+pub(crate) fn block_end(rsx: &str, open: usize) -> usize {
+    let strings = string_mask(rsx);
+    let mut depth = 0;
+    for (index, byte) in rsx.bytes().enumerate().skip(open) {
+        if strings[index] {
+            continue;
+        }
+        match byte {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return index;
+                }
+            }
+            _ => {}
+        }
+    }
+    rsx.len()
+}
+
+// The attributes and text of an element without the contents of any child elements
+pub(crate) fn top_level_attributes(body: &str) -> (Vec<(String, String)>, Vec<String>) {
+    let strings = string_mask(body);
+    let mut top_level = String::new();
+    let mut depth = 0;
+    for (index, c) in body.char_indices() {
+        if !strings[index] {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ if depth == 0 => top_level.push(c),
+                _ => {}
+            }
+            continue;
+        }
+        if depth == 0 {
+            top_level.push(c);
+        }
+    }
+
+    let literal_regex =
+        Regex::new(r#"(?:(r#type|[A-Za-z_][\w-]*|"[\w-]+")\s*:\s*)?"((?:[^"\\]|\\.)*)""#).unwrap();
+    let mut attributes = Vec::new();
+    let mut text = Vec::new();
+    for literal in literal_regex.captures_iter(&top_level) {
+        let value = literal.get(2).unwrap().as_str().to_string();
+        match literal.get(1) {
+            Some(attribute) => {
+                let attribute = attribute
+                    .as_str()
+                    .trim_start_matches("r#")
+                    .trim_matches('"');
+                attributes.push((attribute.to_string(), value));
+            }
+            None => text.push(value),
+        }
+    }
+    // Event handlers are expressions instead of strings
+    let handler_regex = Regex::new(r"\b(on\w+)\s*:").unwrap();
+    for handler in handler_regex.captures_iter(&top_level) {
+        attributes.push((handler[1].to_string(), String::new()));
+    }
+    (attributes, text)
+}
+
+#[test]
+fn interactive_scaffolding() {
+    let rsx = r#"
+form { class: "flex flex-col",
+    input { r#type: "email", placeholder: "{email_placeholder}" }
+    input { r#type: "checkbox", name: "remember_me" }
+    button { class: "rounded", "Log in" }
+    button { r#type: "submit", "Submit" }
+    p { "Press the button {count} times" }
+}"#;
+    let mut props = crate::props::infer_props(rsx);
+    let (rsx, hooks) = add_interactivity(rsx, &mut props, &[], DioxusVersion::V0_5);
+    assert_eq!(
+        hooks,
+        [
+            "let mut email = use_signal(String::new);",
+            "let mut remember_me = use_signal(|| false);"
+        ]
+    );
+    assert!(rsx.contains(
+        "form { prevent_default: \"onsubmit\", onsubmit: move |event| on_submit.call(event), class"
+    ));
+    assert!(rsx.contains(
+        "input { value: \"{email}\", oninput: move |event| email.set(event.value()), r#type"
+    ));
+    assert!(rsx.contains(
+        "checked: remember_me(), oninput: move |event| remember_me.set(event.value() == \"true\"),"
+    ));
+    assert!(rsx.contains("button { onclick: move |event| on_log_in.call(event), class"));
+    assert!(rsx.contains("button { r#type: \"submit\", \"Submit\" }"));
+    let handlers = props
+        .iter()
+        .filter(|prop| prop.optional)
+        .map(|prop| prop.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        handlers,
+        [
+            "#[props(default)] email_placeholder: String",
+            "#[props(default)] on_submit: EventHandler<FormEvent>",
+            "#[props(default)] on_log_in: EventHandler<MouseEvent>"
+        ]
+    );
+
+    let tabs = r#"div { button { "Overview" } button { "Details" } p { "Tab {selected_tab}" } }"#;
+    let mut props = crate::props::infer_props(tabs);
+    let (rsx, hooks) = add_interactivity(
+        tabs,
+        &mut props,
+        &["selected_tab".to_string()],
+        DioxusVersion::V0_5,
+    );
+    assert!(props.is_empty());
+    assert_eq!(hooks, ["let mut selected_tab = use_signal(|| 0);"]);
+    assert!(rsx.contains("button { onclick: move |_| selected_tab.set(1), \"Details\" }"));
+
+    // Names that are not identifiers are turned into identifiers
+    let fields = r#"div {
+    input { r#type: "checkbox", name: "user.email" }
+    input { name: "tags[]" }
+    input { r#type: "checkbox", name: "type" }
+    input { name: "{field}" }
+}"#;
+    let mut props = crate::props::infer_props(fields);
+    let (_, hooks) = add_interactivity(fields, &mut props, &[], DioxusVersion::V0_5);
+    assert_eq!(
+        hooks,
+        [
+            "let mut user_email = use_signal(|| false);",
+            "let mut tags = use_signal(String::new);",
+            "let mut type_value = use_signal(|| false);",
+            "let mut field_2 = use_signal(String::new);"
+        ]
+    );
+}
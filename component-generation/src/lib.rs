@@ -1,134 +1,551 @@
-use core::panic;
 use dioxus_autofmt::write_block_out;
-use kalosm::language::*;
-use regex::Regex;
 use dioxus_rsx_rosetta::{rsx_from_html, Dom};
-use std::{collections::HashSet, io::Write};
-use tokio::sync::OnceCell;
+use futures_util::{Stream, StreamExt};
+use regex::Regex;
 
-const REGEX_CONSTRAINTS: &str = r#"[^\n]+\ncomponents:\n(- [A-Z][a-z]\w+: [\w\d\.\- ]+\n)+HTML:\n[^\n]+\ncomponent html:(\n[A-Z][a-z]\w+:\n[^\n]+)+<\|eot_id\|>"#;
+mod backend;
+pub use backend::*;
+mod batch;
+pub use batch::*;
+mod calls;
+use calls::{mark_components, pass_props, unmark_components};
+mod chat;
+pub use chat::*;
+mod componentize;
+mod config;
+pub use config::*;
+mod edit;
+pub use edit::*;
+mod error;
+mod gallery;
+pub use error::*;
+mod grammar;
+pub use grammar::*;
+mod icons;
+use icons::resolve_icons;
+mod interactive;
+use interactive::add_interactivity;
+mod loops;
+use loops::collapse_repeated;
+mod naming;
+use naming::{escape_keywords, sanitize_placeholders};
+mod props;
+use props::{expand_boolean_attributes, infer_props, unquote_typed_attributes};
+pub use props::{Prop, PropType};
+mod preview;
+use preview::fill_placeholders;
+mod project;
+pub use project::*;
+mod registry;
+pub use registry::*;
+mod site;
+use site::link_routes;
+pub use site::*;
+mod slots;
+use slots::{fill_slots, SLOT_SUFFIX};
+mod tailwind;
+pub use tailwind::*;
+mod target;
+pub use target::*;
+mod validate;
+pub use validate::*;
 
-async fn model() -> Llama {
-    let model = FileSource::huggingface(
-        "Demonthos/llama3".to_string(),
-        "3387b74827b8429717e7e955efe4eaaea061e178".to_string(),
-        "llama3-v2.Q4_K_M.gguf".to_string(),
-    );
-    let tokenizer = FileSource::huggingface(
-        "NousResearch/Meta-Llama-3-8B".to_string(),
-        "main".to_string(),
-        "tokenizer.json".to_string(),
-    );
-    Llama::builder()
-        .with_source(LlamaSource::new(model, tokenizer))
-        .build()
+/// Generate a UI for the prompt with the default model.
+pub async fn generate_ui(prompt: &str) -> Result<PartialState, GenerationError> {
+    UiGenerator::new(KalosmBackend::default_model().await?)
+        .generate(prompt)
         .await
-        .unwrap()
 }
 
-async fn lazy_model() -> Llama {
-    static MODEL: OnceCell<Llama> = OnceCell::const_new();
-    MODEL.get_or_init(model).await.clone()
+/// Generates UIs with a model backend.
+pub struct UiGenerator<B = KalosmBackend> {
+    backend: B,
+    repair_attempts: usize,
+    registry: ComponentRegistry,
+    target: DioxusVersion,
+    chat_template: ChatTemplate,
+    system_prompt: Option<String>,
 }
 
-pub async fn generate_ui(prompt: &str) -> PartialState {
-    let llm = lazy_model().await;
-    let constraints = RegexParser::new(REGEX_CONSTRAINTS).unwrap();
-    let prompt = "<|start_header_id|>user<|end_header_id|>".to_string() + prompt.trim() + "<|eot_id|><|start_header_id|>assistant<|end_header_id|>" + "\nDESCRIPTION:\n";
-    let mut stream = llm
-        .stream_structured_text(&prompt, constraints);
-
-    let mut state = PartialState::new();
-
-    let mut current_line = String::new();
-    while let Some(text) = stream.next().await {
-        current_line.push_str(&text);
-        let lines = current_line.lines().count();
-        if lines > 1 {
-            let mut lines_iter = current_line.lines();
-            for line in (&mut lines_iter).take(lines - 1) {
-                state.process_line(line);
+impl<B: ModelBackend> UiGenerator<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            repair_attempts: 0,
+            registry: ComponentRegistry::default(),
+            target: DioxusVersion::default(),
+            chat_template: ChatTemplate::default(),
+            system_prompt: None,
+        }
+    }
+
+    /// Regenerate the HTML of components that fail validation up to this many times. Defaults to 0.
+    pub fn with_repair_attempts(mut self, repair_attempts: usize) -> Self {
+        self.repair_attempts = repair_attempts;
+        self
+    }
+
+    /// Offer the components in the registry to the model and reuse them when a generated UI declares a component with the same name.
+    pub fn with_registry(mut self, registry: ComponentRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Write the code of generated UIs for this version of dioxus. Defaults to [`DioxusVersion::V0_5`].
+    pub fn with_target(mut self, target: DioxusVersion) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Format prompts with the chat template the model was fine-tuned with. Defaults to [`ChatTemplate::Llama3`].
+    pub fn with_chat_template(mut self, chat_template: ChatTemplate) -> Self {
+        self.chat_template = chat_template;
+        self
+    }
+
+    /// Start every prompt with a system prompt. There is no system prompt by default.
+    pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    pub fn registry(&self) -> &ComponentRegistry {
+        &self.registry
+    }
+
+    pub fn registry_mut(&mut self) -> &mut ComponentRegistry {
+        &mut self.registry
+    }
+
+    /// Generate a UI for the prompt and wait for the whole generation to finish.
+    pub async fn generate(&self, prompt: &str) -> Result<PartialState, GenerationError> {
+        let mut events = Box::pin(self.stream(prompt));
+        while let Some(event) = events.next().await {
+            if let GenerationEvent::Finished(state) = event? {
+                return Ok(state);
             }
-            current_line = lines_iter.next().unwrap().to_string();
         }
+        unreachable!("the event stream always ends with a finished event")
     }
-    state.process_line(&current_line);
-    state.next_section();
 
-    state
+    /// Generate a UI for the prompt and stream events as each part of the UI is finished. The last event is always [`GenerationEvent::Finished`] or an error.
+    ///
+    /// If the registry is not empty, the HTML of each component is generated separately so components from the registry can be reused without generating their HTML.
+    pub fn stream(
+        &self,
+        prompt: &str,
+    ) -> impl Stream<Item = Result<GenerationEvent, GenerationError>> + '_ {
+        let prompt = prompt.trim().to_string();
+
+        async_stream::stream! {
+            let mut state = PartialState::new(prompt, self.target);
+            let grammar = if self.registry.is_empty() {
+                OutputGrammar::ui()
+            } else {
+                OutputGrammar::sections()
+            }
+            .with_chat_template(self.chat_template);
+
+            {
+                let mut sections = Box::pin(self.stream_sections(
+                    &mut state,
+                    "\nDESCRIPTION:\n",
+                    &grammar,
+                ));
+                while let Some(event) = sections.next().await {
+                    let failed = event.is_err();
+                    yield event;
+                    if failed {
+                        return;
+                    }
+                }
+            }
+
+            if !self.registry.is_empty() {
+                for await event in self.stream_component_html(&mut state, &[]) {
+                    yield event;
+                }
+            }
+
+            for await event in self.finish(state) {
+                yield event;
+            }
+        }
+    }
+
+    // Stream the response of the model into the state. The response starts after `response_start`
+    fn stream_sections<'a>(
+        &'a self,
+        state: &'a mut PartialState,
+        response_start: &str,
+        grammar: &OutputGrammar,
+    ) -> impl Stream<Item = Result<GenerationEvent, GenerationError>> + 'a {
+        let mut stream = self
+            .backend
+            .stream_structured_text(&self.prompt(&state.prompt, response_start), grammar);
+        let grammar = grammar.clone();
+
+        async_stream::stream! {
+            let stop_token = self.chat_template.stop_token();
+            let mut response = String::new();
+            let mut current_line = String::new();
+            // A line that ends with the stop token is held back with any empty lines after it, until the rest of the response shows if the stop token ends the response or closes an element like Mistral's `</s>`
+            let mut held = Vec::<String>::new();
+            let mut ended = false;
+            while !ended {
+                let mut ready = Vec::new();
+                match stream.next().await {
+                    Some(text) => {
+                        response.push_str(&text);
+                        current_line.push_str(&text);
+                        // Process every finished line and keep the rest for the next chunk of text
+                        while let Some((line, rest)) = current_line.split_once('\n') {
+                            let line = line.to_string();
+                            current_line = rest.to_string();
+                            if !held.is_empty() && line.trim().is_empty() {
+                                held.push(line);
+                                continue;
+                            }
+                            ready.append(&mut held);
+                            if line.ends_with(stop_token) {
+                                held.push(line);
+                            } else {
+                                ready.push(line);
+                            }
+                        }
+                    }
+                    None => {
+                        ended = true;
+                        let last = std::mem::take(&mut current_line);
+                        if last.trim().is_empty() && !held.is_empty() {
+                            held[0] = self.chat_template.strip_stop_token(&held[0]).to_string();
+                            ready.append(&mut held);
+                            ready.push(last);
+                        } else {
+                            ready.append(&mut held);
+                            ready.push(self.chat_template.strip_stop_token(&last).to_string());
+                        }
+                    }
+                }
+                for line in ready {
+                    match state.process_line(&line) {
+                        Ok(Some(event)) => yield Ok(event),
+                        Ok(None) => {}
+                        Err(err) => {
+                            yield Err(err);
+                            return;
+                        }
+                    }
+                }
+            }
+            if let Err(err) = check_complete(&grammar, &response) {
+                yield Err(err);
+                return;
+            }
+            if let Some(event) = state.next_section() {
+                yield Ok(event);
+            }
+        }
+    }
+
+    // Fill in the HTML of every declared component. Components are taken from `previous` first, then from the registry, and only generated if neither has them
+    fn stream_component_html<'a>(
+        &'a self,
+        state: &'a mut PartialState,
+        previous: &'a [Component],
+    ) -> impl Stream<Item = Result<GenerationEvent, GenerationError>> + 'a {
+        async_stream::stream! {
+            for index in 0..state.components.len() {
+                let name = state.components[index].name.clone();
+                if let Some(existing) = previous.iter().find(|component| component.name == name) {
+                    state.components[index].html = existing.html.clone();
+                } else if let Some(registered) = self.registry.get(&name) {
+                    let component = &mut state.components[index];
+                    component.description = registered.description.clone();
+                    component.html = registered.html.clone();
+                    component.state = registered.state.clone();
+                } else {
+                    match self.component_html(state, index).await {
+                        Ok(html) => state.components[index].html = html,
+                        Err(err) => {
+                            yield Err(err);
+                            return;
+                        }
+                    }
+                }
+                let component = &state.components[index];
+                yield Ok(GenerationEvent::ComponentHtmlComplete {
+                    name: component.name.clone(),
+                    html: component.html.clone(),
+                });
+            }
+        }
+    }
+
+    // Validate the generated UI, repair any invalid components and finish the generation
+    fn finish(
+        &self,
+        mut state: PartialState,
+    ) -> impl Stream<Item = Result<GenerationEvent, GenerationError>> + '_ {
+        async_stream::stream! {
+            state.sanitize_names();
+            state.validate();
+            for _ in 0..self.repair_attempts {
+                let failing = state
+                    .components
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, component)| !component.is_valid())
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+                if failing.is_empty() {
+                    break;
+                }
+                for index in failing {
+                    let html = match self.component_html(&state, index).await {
+                        Ok(html) => html,
+                        Err(err) => {
+                            yield Err(err);
+                            return;
+                        }
+                    };
+                    state.components[index].html = sanitize_placeholders(&html);
+                    let diagnostics = state.components[index].validate_with(&state.components);
+                    let component = &mut state.components[index];
+                    component.diagnostics = diagnostics;
+                    yield Ok(GenerationEvent::ComponentRepaired {
+                        name: component.name.clone(),
+                        html: component.html.clone(),
+                        diagnostics: component.diagnostics.clone(),
+                    });
+                }
+            }
+
+            yield Ok(GenerationEvent::Finished(state));
+        }
+    }
+
+    // Generate the HTML for a single component from the rest of the state
+    async fn component_html(
+        &self,
+        state: &PartialState,
+        index: usize,
+    ) -> Result<String, GenerationError> {
+        let prompt = self.prompt(&state.prompt, &state.component_prefix(index));
+        let grammar = state
+            .component_grammar(index)
+            .with_chat_template(self.chat_template);
+        let html = self
+            .backend
+            .stream_structured_text(&prompt, &grammar)
+            .collect::<String>()
+            .await;
+        check_complete(&grammar, &html)?;
+        Ok(self.chat_template.strip_stop_token(html.trim()).to_string())
+    }
+
+    // The full prompt in the chat template. The response of the model starts after `response_start`
+    fn prompt(&self, prompt: &str, response_start: &str) -> String {
+        self.chat_template.prompt(
+            self.system_prompt.as_deref(),
+            &self.user_prompt(prompt),
+            response_start,
+        )
+    }
+
+    // The prompt with the components from the registry listed as components the model can reuse
+    fn user_prompt(&self, prompt: &str) -> String {
+        if self.registry.is_empty() {
+            return prompt.to_string();
+        }
+        let mut user_prompt = format!(
+            "{prompt}\n\nThese components already exist. Reuse them by name where they fit:\n"
+        );
+        for component in self.registry.components() {
+            user_prompt += &format!("- {}: {}\n", component.name, component.description);
+        }
+        user_prompt
+    }
 }
 
+// A response that follows the grammar so far but is not finished was cut off, usually by the `max_chunks` limit. Responses that don't follow the grammar at all come from backends that don't enforce it and are parsed as they are
+fn check_complete(grammar: &OutputGrammar, response: &str) -> Result<(), GenerationError> {
+    let mut state = grammar.state();
+    if state.push_str(response).is_ok() && !state.is_finished() {
+        return Err(GenerationError::Truncated {
+            raw_output: response.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Progress while a UI is being generated.
 #[derive(Debug)]
+pub enum GenerationEvent {
+    /// A chunk of the description of the UI
+    Description(String),
+    /// The model declared a component it will write HTML for later
+    ComponentDeclared { name: String, description: String },
+    /// The HTML for the top level UI is finished
+    HtmlComplete { html: String },
+    /// The HTML for a component is finished
+    ComponentHtmlComplete { name: String, html: String },
+    /// The HTML for a component that failed validation was regenerated. The diagnostics have no errors if the new HTML is valid.
+    ComponentRepaired {
+        name: String,
+        html: String,
+        diagnostics: Vec<Diagnostic>,
+    },
+    /// The generation is finished
+    Finished(PartialState),
+}
+
+#[derive(Debug, Clone)]
 pub struct PartialState {
     current_section: Section,
+    prompt: String,
+    raw_output: String,
     pub description: String,
     pub html: String,
     current_component_index: Option<usize>,
     components: Vec<Component>,
+    app_diagnostics: Vec<Diagnostic>,
+    target: DioxusVersion,
 }
 
 impl PartialState {
-    fn new() -> Self {
+    fn new(prompt: String, target: DioxusVersion) -> Self {
         Self {
             current_section: Section::Description,
+            prompt,
+            raw_output: String::new(),
             description: String::new(),
             html: String::new(),
             current_component_index: None,
             components: Vec::new(),
+            app_diagnostics: Vec::new(),
+            target,
+        }
+    }
+
+    /// The `app` component that is launched in `main`. It takes no props, so its placeholders are filled with sample values and every component it renders gets its required props.
+    pub fn app_component(&self) -> Result<String, GenerationError> {
+        let html = fill_placeholders(&self.html, &[]);
+        let block = html_to_rsx(&html)?;
+        let code = write_component("app", "", &block, &[], self.target, true);
+        Ok(pass_props(&code, &component_props(&self.components)))
+    }
+
+    /// The version of dioxus the code is written for
+    pub fn target(&self) -> DioxusVersion {
+        self.target
+    }
+
+    /// Write the code of the app and every component for another version of dioxus
+    pub fn set_target(&mut self, target: DioxusVersion) {
+        self.target = target;
+        for component in &mut self.components {
+            component.target = target;
         }
     }
 
-    pub fn app_component(&self) -> String {
-        let nodes = Dom::parse(&self.html).unwrap();
-        let rsx = rsx_from_html(&nodes);
-        let block = write_block_out(&rsx).unwrap();
-        rsx_to_component("app", "", &block)
+    /// The text the model generated so far
+    pub fn raw_output(&self) -> &str {
+        &self.raw_output
     }
 
     pub fn components(&self) -> &[Component] {
         &self.components
     }
 
-    fn next_section(&mut self) {
-        if let Some(next_section) = self.current_section.next_section() {
-            #[allow(clippy::single_match)]
-            match next_section {
-                Section::Description => {
-                    println!();
-                }
-                Section::Components => {
-                    println!("I think I will need components for this...");
-                }
-                _ => {}
-            }
+    /// The prompt the UI was generated from
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
 
-            self.current_section = next_section;
-        }
+    // The response of the model up to the HTML of a component
+    fn component_prefix(&self, index: usize) -> String {
+        let component_list = self
+            .components
+            .iter()
+            .map(|component| format!("- {}: {}\n", component.name, component.description))
+            .collect::<String>();
+        let interactive = self
+            .components
+            .iter()
+            .filter(|component| !component.state.is_empty())
+            .map(|component| format!("- {}: {}\n", component.name, component.state.join(", ")))
+            .collect::<String>();
+        let interactive = if interactive.is_empty() {
+            interactive
+        } else {
+            format!("interactive:\n{interactive}")
+        };
+        format!(
+            "\nDESCRIPTION:\n{}\ncomponents:\n{}{}HTML:\n{}\ncomponent html:\n{}:\n",
+            self.description, component_list, interactive, self.html, self.components[index].name
+        )
+    }
+
+    // The grammar for the HTML of a component when it is generated on its own
+    fn component_grammar(&self, index: usize) -> OutputGrammar {
+        OutputGrammar::component_html(
+            self.components
+                .iter()
+                .map(|component| component.name.clone()),
+            &self.components[index].name,
+        )
     }
 
-    fn process_line(&mut self, line: &str) {
+    fn next_section(&mut self) -> Option<GenerationEvent> {
+        let next_section = self.current_section.next_section()?;
+        let event = match self.current_section {
+            Section::HTML => Some(GenerationEvent::HtmlComplete {
+                html: self.html.clone(),
+            }),
+            _ => None,
+        };
+        self.current_section = next_section;
+
+        event
+    }
+
+    fn process_line(&mut self, line: &str) -> Result<Option<GenerationEvent>, GenerationError> {
+        self.raw_output.push_str(line);
+        self.raw_output.push('\n');
+
         if line.trim().is_empty() {
-            return;
+            return Ok(None);
         }
 
         if let Some(next_section) = self.current_section.next_section() {
             if line.to_lowercase().trim().replace([':', '-'], "")
                 == next_section.identifier().to_lowercase()
             {
-                self.next_section();
-
-                return;
+                return Ok(self.next_section());
             }
         }
 
-        match self.current_section {
+        // The interactive section is optional
+        if matches!(self.current_section, Section::Components)
+            && line.to_lowercase().trim().trim_end_matches(':') == Section::Interactive.identifier()
+        {
+            self.current_section = Section::Interactive;
+            return Ok(None);
+        }
+
+        let event = match self.current_section {
             Section::Description => {
                 self.description.push_str(line);
-                print!("{}", line);
-                std::io::stdout().flush().unwrap();
+                Some(GenerationEvent::Description(line.to_string()))
             }
             Section::Components => {
-                let (before_colon, after_colon) = line.trim().split_once(':').unwrap();
+                let (before_colon, after_colon) = line.trim().split_once(':').ok_or_else(|| {
+                    GenerationError::MalformedComponent {
+                        line: line.to_string(),
+                        raw_output: self.raw_output.clone(),
+                    }
+                })?;
                 let name = before_colon
                     .strip_prefix('-')
                     .unwrap_or(before_colon)
@@ -139,24 +556,60 @@ impl PartialState {
                     name,
                     description,
                     html: String::new(),
+                    state: Vec::new(),
+                    diagnostics: Vec::new(),
+                    target: self.target,
+                    links: Vec::new(),
+                };
+                let event = GenerationEvent::ComponentDeclared {
+                    name: component.name.clone(),
+                    description: component.description.clone(),
                 };
-                println!("- {} ({})", component.name, component.description);
                 self.components.push(component);
+                Some(event)
+            }
+            Section::Interactive => {
+                let (before_colon, after_colon) = line.trim().split_once(':').ok_or_else(|| {
+                    GenerationError::MalformedComponent {
+                        line: line.to_string(),
+                        raw_output: self.raw_output.clone(),
+                    }
+                })?;
+                let name = before_colon
+                    .strip_prefix('-')
+                    .unwrap_or(before_colon)
+                    .trim();
+                let index = self
+                    .components
+                    .iter()
+                    .position(|component| component.name == name)
+                    .ok_or_else(|| GenerationError::UnknownComponent {
+                        name: name.to_string(),
+                        declared: self.components.iter().map(|x| x.name.clone()).collect(),
+                        raw_output: self.raw_output.clone(),
+                    })?;
+                self.components[index].state = after_colon
+                    .split(',')
+                    .map(|state| state.trim().to_string())
+                    .filter(|state| !state.is_empty())
+                    .collect();
+                None
             }
             Section::HTML => {
                 self.html.push_str(line.trim());
+                None
             }
             Section::ComponentHTML => {
                 let html = line.trim().to_string();
                 match self.current_component_index {
                     Some(index) => {
-                        // Only keep the HTML up to the end of the text
-                        let html = html
-                            .split_once("<|eot_id|>")
-                            .map(|x| x.0)
-                            .unwrap_or(&html);
-                        self.components[index].html = html.to_string();
+                        let component = &mut self.components[index];
+                        component.html = html.to_string();
                         self.current_component_index = None;
+                        Some(GenerationEvent::ComponentHtmlComplete {
+                            name: component.name.clone(),
+                            html: component.html.clone(),
+                        })
                     }
                     None => {
                         let trimmed_line = line
@@ -164,49 +617,115 @@ impl PartialState {
                             .filter(|c| c.is_ascii_alphabetic())
                             .collect::<String>()
                             .to_lowercase();
+                        // Prefer an exact match over names that only differ in case or punctuation
+                        let exact = line.trim().trim_end_matches(':').trim();
                         let index = self
                             .components
                             .iter()
-                            .position(|x| x.name.to_lowercase().trim() == trimmed_line)
-                            .unwrap_or_else(|| {
-                                panic!(
-                                    "Component {} not found in {:?}",
-                                    line,
-                                    self.components
-                                        .iter()
-                                        .map(|x| x.name.to_lowercase())
-                                        .collect::<Vec<String>>()
-                                )
-                            });
-                        println!("Creating HTML for {}...", self.components[index].name);
+                            .position(|x| x.name == exact)
+                            .or_else(|| {
+                                self.components
+                                    .iter()
+                                    .position(|x| x.name.to_lowercase().trim() == trimmed_line)
+                            })
+                            .ok_or_else(|| GenerationError::UnknownComponent {
+                                name: line.trim().trim_end_matches(':').to_string(),
+                                declared: self.components.iter().map(|x| x.name.clone()).collect(),
+                                raw_output: self.raw_output.clone(),
+                            })?;
                         self.current_component_index = Some(index);
+                        None
                     }
                 }
             }
-        }
+        };
+
+        Ok(event)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Component {
     pub name: String,
     pub description: String,
     pub html: String,
+    /// State the model declared for the component in the interactive section. Each one is stored in a signal instead of a prop.
+    pub state: Vec<String>,
+    diagnostics: Vec<Diagnostic>,
+    target: DioxusVersion,
+    // The path and route of each page of a site. Links to these paths are rendered as router links
+    links: Vec<(String, String)>,
 }
 
 impl Component {
-    pub fn component_string(&self) -> String {
-        let nodes = Dom::parse(&self.html).unwrap();
-        let rsx = rsx_from_html(&nodes);
-        let block = write_block_out(&rsx).unwrap();
-        rsx_to_component(&self.name, &self.description, &block)
+    /// The code of the component on its own. Use [`Component::component_string_with`] if it renders other components.
+    pub fn component_string(&self) -> Result<String, GenerationError> {
+        self.component_string_with(&[])
+    }
+
+    /// The code of the component when it can render the other components. Calls to them get their required props.
+    pub fn component_string_with(
+        &self,
+        components: &[Component],
+    ) -> Result<String, GenerationError> {
+        let block = html_to_rsx(&self.html)?;
+        let code = rsx_to_component(
+            &self.name,
+            &self.description,
+            &block,
+            &self.state,
+            self.target,
+        );
+        let others = components
+            .iter()
+            .filter(|component| component.name != self.name)
+            .cloned()
+            .collect::<Vec<_>>();
+        let code = pass_props(&code, &component_props(&others));
+        Ok(link_routes(&code, &self.links))
+    }
+
+    /// The props of the generated component
+    pub fn props(&self) -> Result<Vec<Prop>, GenerationError> {
+        let rsx = html_to_rsx(&self.html)?;
+        // Find the props the same way as `rsx_to_component`
+        let rsx = fill_slots(&sanitize_placeholders(&rsx));
+        let (rsx, _) = collapse_repeated(&rsx, &self.name);
+        let mut props = infer_props(&rsx);
+        add_interactivity(&rsx, &mut props, &self.state, self.target);
+        Ok(props)
     }
 }
 
-#[derive(Debug)]
+fn html_to_rsx(html: &str) -> Result<String, GenerationError> {
+    // Unknown icons are reported when the code is validated
+    let (html, _) = resolve_icons(html);
+    let html = html.as_str();
+    let (marked, components) = mark_components(&expand_boolean_attributes(html));
+    let nodes = Dom::parse(&marked).map_err(|err| GenerationError::InvalidHtml {
+        message: err.to_string(),
+        html: html.to_string(),
+    })?;
+    let rsx = rsx_from_html(&nodes);
+    let rsx = write_block_out(&rsx).ok_or_else(|| GenerationError::InvalidRsx {
+        html: html.to_string(),
+    })?;
+    Ok(unmark_components(&rsx, &components))
+}
+
+// The name and props of every component that converts
+pub(crate) fn component_props(components: &[Component]) -> Vec<(String, Vec<Prop>)> {
+    components
+        .iter()
+        .filter_map(|component| Some((component.name.clone(), component.props().ok()?)))
+        .collect()
+}
+
+#[derive(Debug, Clone)]
 enum Section {
     Description,
     Components,
+    Interactive,
     #[allow(clippy::upper_case_acronyms)]
     HTML,
     ComponentHTML,
@@ -217,6 +736,7 @@ impl Section {
         match self {
             Section::Description => "description",
             Section::Components => "components",
+            Section::Interactive => "interactive",
             Section::HTML => "html",
             Section::ComponentHTML => "component html",
         }
@@ -225,29 +745,73 @@ impl Section {
     fn next_section(&self) -> Option<Section> {
         match self {
             Section::Description => Some(Section::Components),
-            Section::Components => Some(Section::HTML),
+            Section::Components | Section::Interactive => Some(Section::HTML),
             Section::HTML => Some(Section::ComponentHTML),
             Section::ComponentHTML => None,
         }
     }
 }
 
-fn rsx_to_component(name: &str, description: &str, rsx: &str) -> String {
+fn rsx_to_component(
+    name: &str,
+    description: &str,
+    rsx: &str,
+    state: &[String],
+    target: DioxusVersion,
+) -> String {
+    write_component(name, description, rsx, state, target, false)
+}
+
+// Write the code of a component. A `root` component is launched without props, so the data of its loops is stored in local variables instead of props
+fn write_component(
+    name: &str,
+    description: &str,
+    rsx: &str,
+    state: &[String],
+    target: DioxusVersion,
+    root: bool,
+) -> String {
+    // Placeholders are renamed to the snake case names of their props
+    let rsx = fill_slots(&sanitize_placeholders(rsx));
+    // Repeated siblings are rendered from sample data in a loop
+    let (rsx, lists) = collapse_repeated(&rsx, name);
     // Find any parameters for the function
-    // Find all occurrences of {parameter} inside a string
-    let re = Regex::new(r#""[^"]*\{([a-z_]+)\}[^"]*"#).unwrap();
-    let mut parameters = HashSet::new();
-    for cap in re.captures_iter(rsx) {
-        parameters.insert(cap.get(1).unwrap().as_str().to_string());
-    }
+    let mut props = infer_props(&rsx);
+    let (rsx, hooks) = add_interactivity(&rsx, &mut props, state, target);
+    let rsx = unquote_typed_attributes(&rsx, &props);
 
     // Replace all occurrences of "{children}" with {children}
     let children_regex = Regex::new(r#""\{\s*children\s*\}""#).unwrap();
-    let rsx = children_regex.replace_all(rsx, "{children}").to_string();
-
-    println!("\n");
+    let rsx = children_regex.replace_all(&rsx, "{children}").to_string();
+    // Render slots unquoted like children. "{header_slot}" becomes {header}
+    let mut rsx = rsx;
+    for prop in &props {
+        if prop.ty != PropType::Element || prop.name == "children" {
+            continue;
+        }
+        let slot_regex = Regex::new(&format!(
+            r#"(:\s*)?"\{{\s*{}{SLOT_SUFFIX}\s*\}}""#,
+            regex::escape(&prop.name)
+        ))
+        .unwrap();
+        rsx = slot_regex
+            .replace_all(&rsx, |slot: &regex::Captures| match slot.get(1) {
+                Some(_) => slot[0].to_string(),
+                None => format!("{{{}}}", prop.name),
+            })
+            .to_string();
+    }
+    let names = props
+        .iter()
+        .map(|prop| prop.name.as_str())
+        .chain(state.iter().map(String::as_str));
+    let rsx = escape_keywords(&rsx, names);
 
     let mut component_string = String::new();
+    for list in &lists {
+        component_string += &list.definitions();
+        component_string += "\n";
+    }
     // Print the docstring
     if !description.trim().is_empty() {
         component_string += &format!("/// {}", description);
@@ -255,27 +819,164 @@ fn rsx_to_component(name: &str, description: &str, rsx: &str) -> String {
     component_string += "\n#[component]";
 
     // Print the function signature
-    component_string += &format!("\nfn {}(", name);
-    for (i, parameter) in parameters.iter().enumerate() {
-        if parameter == "children" {
-            component_string += "children: Element";
-        } else {
-            component_string += &format!("{parameter}: String");
-        }
-        if i < parameters.len() - 1 {
-            component_string += ", ";
+    let list_props = if root { &[][..] } else { &lists[..] };
+    component_string += &format!("\n{} {{\n", target.signature(name, &props, list_props));
+    if root {
+        for list in &lists {
+            component_string += &format!("    {}\n", list.binding());
         }
     }
-    component_string += ") -> Element {\n";
-    component_string += "    rsx! {";
+    for hook in hooks {
+        component_string += &format!("    {hook}\n");
+    }
     // Add an extra level of indentation to the RSX
     let rsx = rsx
         .lines()
         .map(|line| "    ".to_string() + line)
         .collect::<Vec<String>>()
         .join("\n");
-    component_string += &rsx;
-    component_string += "\n    }\n}";
+    component_string += &format!("    {}\n}}", target.render(&rsx));
 
     component_string
 }
+
+#[tokio::test]
+async fn parse_scripted_response() {
+    let backend = ScriptedBackend::new().with_response(
+        "A simple landing page with a navigation bar.
+components:
+- NavBar: A navigation bar with links
+HTML:
+<div><NavBar>{children}</NavBar></div>
+component html:
+NavBar:
+<nav class=\"flex\">{children}</nav><|eot_id|>",
+    );
+    let state = UiGenerator::new(backend)
+        .generate("a landing page")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        state.description,
+        "A simple landing page with a navigation bar."
+    );
+    assert_eq!(state.html, "<div><NavBar>{children}</NavBar></div>");
+    let components = state.components();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].name, "NavBar");
+    assert_eq!(components[0].description, "A navigation bar with links");
+    assert_eq!(components[0].html, "<nav class=\"flex\">{children}</nav>");
+}
+
+#[tokio::test]
+async fn stream_scripted_events() {
+    let backend = ScriptedBackend::new().with_response(
+        "A profile card.
+components:
+- Avatar: A round user avatar
+HTML:
+<div><Avatar/></div>
+component html:
+Avatar:
+<img class=\"rounded-full\" src=\"{avatar_url}\"/><|eot_id|>",
+    );
+    let events = UiGenerator::new(backend)
+        .stream("a profile card")
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(events.len(), 5);
+    assert!(matches!(&events[0], GenerationEvent::Description(text) if text == "A profile card."));
+    assert!(
+        matches!(&events[1], GenerationEvent::ComponentDeclared { name, .. } if name == "Avatar")
+    );
+    assert!(
+        matches!(&events[2], GenerationEvent::HtmlComplete { html } if html == "<div><Avatar/></div>")
+    );
+    assert!(
+        matches!(&events[3], GenerationEvent::ComponentHtmlComplete { name, .. } if name == "Avatar")
+    );
+    assert!(matches!(&events[4], GenerationEvent::Finished(_)));
+}
+
+#[tokio::test]
+async fn mistral_stop_token_is_not_strikethrough() {
+    let response = "A price tag.
+components:
+- Price: The sale price
+HTML:
+<div><Price/></div><s>$120</s>
+component html:
+Price:
+<p><s>$99</s> $49</p></s>";
+    assert!(OutputGrammar::ui()
+        .with_chat_template(ChatTemplate::Mistral)
+        .accepts(response));
+    let backend = ScriptedBackend::new().with_response(response);
+    let state = UiGenerator::new(backend)
+        .with_chat_template(ChatTemplate::Mistral)
+        .generate("a price tag")
+        .await
+        .unwrap();
+    assert_eq!(state.html, "<div><Price/></div><s>$120</s>");
+    assert_eq!(state.components()[0].html, "<p><s>$99</s> $49</p>");
+}
+
+#[tokio::test]
+async fn chat_template_reaches_every_grammar() {
+    let mut registry = ComponentRegistry::new();
+    let button = Component {
+        name: "Button".to_string(),
+        description: "A rounded button".to_string(),
+        html: "<button class=\"rounded\">Buy</button>".to_string(),
+        state: Vec::new(),
+        diagnostics: Vec::new(),
+        target: DioxusVersion::V0_5,
+        links: Vec::new(),
+    };
+    registry.insert(&button).unwrap();
+
+    // With a registry the sections and the HTML of each component are separate responses
+    let backend = ScriptedBackend::new()
+        .with_response(
+            "A price tag.
+components:
+- Price: The sale price
+- Button: A buy button
+HTML:
+<div><Price/><Button/></div>
+",
+        )
+        .with_response("<p><s>$99</s> $49</p></s>");
+    let state = UiGenerator::new(backend.clone())
+        .with_chat_template(ChatTemplate::Mistral)
+        .with_registry(registry)
+        .generate("a price tag")
+        .await
+        .unwrap();
+    assert_eq!(state.components()[0].html, "<p><s>$99</s> $49</p>");
+    assert!(backend.prompts()[1].starts_with("[INST] "));
+}
+
+#[tokio::test]
+async fn undeclared_component_is_an_error() {
+    let backend = ScriptedBackend::new().with_response(
+        "A footer.
+components:
+- Footer: The page footer
+HTML:
+<Footer/>
+component html:
+Header:
+<header></header><|eot_id|>",
+    );
+    let error = UiGenerator::new(backend)
+        .generate("a footer")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(&error, GenerationError::UnknownComponent { name, .. } if name == "Header"));
+    assert!(error.raw_output().unwrap().ends_with("Header:\n"));
+}
@@ -0,0 +1,419 @@
+use crate::componentize::{attribute_prop_name, base_name, text_prop_name};
+use crate::interactive::{block_end, string_mask};
+use crate::naming::{component_name, prop_name};
+use convert_case::{Case, Casing};
+use regex::Regex;
+
+// Runs of identical siblings shorter than this are left as they are
+const MIN_REPEATS: usize = 3;
+
+/// Sample data for a run of repeated elements that was turned into a `for` loop. The data is a struct with a field for each string that differed between the elements, a constant with the original values and a prop that defaults to the constant.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DataList {
+    /// The name of the struct for one element like `PricingCard`
+    pub(crate) name: String,
+    pub(crate) fields: Vec<String>,
+    /// The values of the fields for each element
+    pub(crate) rows: Vec<Vec<String>>,
+}
+
+impl DataList {
+    /// The name of the constant with the sample data like `PRICING_CARDS`
+    pub(crate) fn constant(&self) -> String {
+        format!("{}S", self.name.to_case(Case::UpperSnake))
+    }
+
+    /// The name of the prop with the data like `pricing_cards`
+    pub(crate) fn prop(&self) -> String {
+        format!("{}s", self.name.to_case(Case::Snake))
+    }
+
+    /// The parameter of the component function for the prop
+    pub(crate) fn param(&self) -> String {
+        format!(
+            "#[props(default = {}.to_vec())] {}: Vec<{}>",
+            self.constant(),
+            self.prop(),
+            self.name
+        )
+    }
+
+    /// The local variable with the data for components that have no props
+    pub(crate) fn binding(&self) -> String {
+        format!("let {} = {};", self.prop(), self.constant())
+    }
+
+    /// The struct and the constant with the sample data
+    pub(crate) fn definitions(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| format!("    pub {field}: &'static str,\n"))
+            .collect::<String>();
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let values = self
+                    .fields
+                    .iter()
+                    .zip(row)
+                    .map(|(field, value)| format!("{field}: \"{value}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("    {} {{ {values} }},\n", self.name)
+            })
+            .collect::<String>();
+        format!(
+            "#[derive(Debug, Clone, Copy, PartialEq)]\npub struct {} {{\n{fields}}}\n\nconst {}: &[{}] = &[\n{rows}];\n",
+            self.name,
+            self.constant(),
+            self.name
+        )
+    }
+}
+
+/// Turn runs of structurally identical sibling elements that only differ in their strings into a `for` loop over a [`DataList`]. `component` is the name of the component the rsx is for, which is used to name the data.
+pub(crate) fn collapse_repeated(rsx: &str, component: &str) -> (String, Vec<DataList>) {
+    let element_regex = Regex::new(r"\b([A-Za-z][A-Za-z0-9_]*)\s*\{").unwrap();
+    let strings = string_mask(rsx);
+
+    // Every element with its start and the index of its closing brace
+    let elements = element_regex
+        .captures_iter(rsx)
+        .filter_map(|element| {
+            let whole = element.get(0).unwrap();
+            if strings[whole.start()] {
+                return None;
+            }
+            let close = block_end(rsx, whole.end() - 1);
+            (close < rsx.len()).then(|| (element[1].to_string(), whole.start(), close))
+        })
+        .collect::<Vec<_>>();
+
+    let mut lists: Vec<DataList> = Vec::new();
+    let mut output = String::new();
+    let mut copied = 0;
+    let mut index = 0;
+    while index < elements.len() {
+        let (tag, start, close) = &elements[index];
+        if *start < copied {
+            index += 1;
+            continue;
+        }
+        // Siblings follow each other with only whitespace or commas between them, so no brace between them changes the depth
+        let shape = element_shape(&rsx[*start..=*close]);
+        let mut run = vec![index];
+        let mut end = *close;
+        for (next, (_, next_start, next_close)) in elements.iter().enumerate().skip(index + 1) {
+            if *next_start <= end {
+                continue;
+            }
+            let between = &rsx[end + 1..*next_start];
+            if !between.chars().all(|c| c.is_whitespace() || c == ',')
+                || element_shape(&rsx[*next_start..=*next_close]) != shape
+            {
+                break;
+            }
+            run.push(next);
+            end = *next_close;
+        }
+
+        let copies = run
+            .iter()
+            .map(|&element| &rsx[elements[element].1..=elements[element].2])
+            .collect::<Vec<_>>();
+        let line_start = rsx[..*start].rfind('\n').map_or(0, |line| line + 1);
+        let line = &rsx[line_start..*start];
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let collapsed =
+            match run.len() >= MIN_REPEATS && !copies.iter().any(|copy| has_behavior(copy)) {
+                true => collapse_run(&copies, tag, component, indent, &lists),
+                false => None,
+            };
+        let Some((code, list)) = collapsed else {
+            index += 1;
+            continue;
+        };
+        output += &rsx[copied..*start];
+        output += &code;
+        lists.push(list);
+        copied = end + 1;
+        index = run[run.len() - 1] + 1;
+    }
+    output += &rsx[copied..];
+    (output, lists)
+}
+
+// Interactive elements get a signal or handler for each copy, and links to other pages become router links, so runs that contain them are left as they are
+fn has_behavior(copy: &str) -> bool {
+    let element_regex = Regex::new(r"\b([A-Za-z][A-Za-z0-9_]*)\s*\{").unwrap();
+    let href_regex = Regex::new(r#"\bhref\s*:\s*"/"#).unwrap();
+    let strings = string_mask(copy);
+    element_regex.captures_iter(copy).any(|element| {
+        !strings[element.get(0).unwrap().start()] && INTERACTIVE_TAGS.contains(&&element[1])
+    }) || href_regex
+        .find_iter(copy)
+        .any(|href| !strings[href.start()])
+}
+
+const INTERACTIVE_TAGS: &[&str] = &["input", "textarea", "select", "button", "form"];
+
+// Build the loop for a run of elements, or `None` if no string differs or a differing string is formatted
+fn collapse_run(
+    copies: &[&str],
+    tag: &str,
+    component: &str,
+    indent: &str,
+    lists: &[DataList],
+) -> Option<(String, DataList)> {
+    let literals = copies
+        .iter()
+        .map(|copy| string_literals(copy))
+        .collect::<Vec<_>>();
+    let mut fields = Vec::new();
+    let mut rows = vec![Vec::new(); copies.len()];
+    // The field each literal of the first copy is replaced with
+    let mut replacements = Vec::new();
+    for (index, literal) in literals[0].iter().enumerate() {
+        let values = literals
+            .iter()
+            .map(|literals| literals[index].value.clone())
+            .collect::<Vec<_>>();
+        if values.iter().all(|value| *value == literal.value) {
+            continue;
+        }
+        if values.iter().any(|value| value.contains(['{', '}'])) {
+            return None;
+        }
+        let base = match &literal.attribute {
+            Some(attribute) => attribute_prop_name(&literal.tag, attribute),
+            None => text_prop_name(&literal.tag),
+        };
+        let base = prop_name(base);
+        let mut field = base.clone();
+        let mut suffix = 2;
+        while fields.contains(&field) {
+            field = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+        for (row, value) in rows.iter_mut().zip(values) {
+            row.push(value);
+        }
+        replacements.push((literal.start, literal.end, field.clone()));
+        fields.push(field);
+    }
+    if fields.is_empty() {
+        return None;
+    }
+
+    let base = component_name(&format!("{component} {}", base_name(tag)));
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while lists.iter().any(|list| list.name == name) {
+        name = format!("{base}{suffix}");
+        suffix += 1;
+    }
+    let list = DataList { name, fields, rows };
+
+    let item = base_name(tag).to_case(Case::Snake);
+    let mut template = String::new();
+    let mut copied = 0;
+    for (start, end, field) in replacements {
+        template += &copies[0][copied..start];
+        template += &format!("\"{{{item}.{field}}}\"");
+        copied = end;
+    }
+    template += &copies[0][copied..];
+    // The lines after the first line of the element are already indented to the depth of the element
+    let code = format!(
+        "for {item} in {}.iter() {{\n{indent}    {}\n{indent}}}",
+        list.prop(),
+        template.replace('\n', "\n    ")
+    );
+    Some((code, list))
+}
+
+// The element with the contents of every string removed and the whitespace collapsed. Copies of an element that only differ in their strings have the same shape
+fn element_shape(element: &str) -> String {
+    let strings = string_mask(element);
+    let mut shape = String::new();
+    let mut last_whitespace = false;
+    for (index, c) in element.char_indices() {
+        if strings[index] {
+            if index == 0 || !strings[index - 1] {
+                shape += "\"\"";
+            }
+            continue;
+        }
+        if c.is_whitespace() {
+            if !last_whitespace {
+                shape.push(' ');
+            }
+            last_whitespace = true;
+            continue;
+        }
+        last_whitespace = false;
+        shape.push(c);
+    }
+    shape
+}
+
+// A string literal in an element with the element it is in and the attribute it is assigned to
+struct Literal {
+    start: usize,
+    end: usize,
+    value: String,
+    tag: String,
+    attribute: Option<String>,
+}
+
+fn string_literals(element: &str) -> Vec<Literal> {
+    let literal_regex = Regex::new(r#""((?:[^"\\]|\\.)*)""#).unwrap();
+    let open_regex = Regex::new(r"\b([A-Za-z][A-Za-z0-9_]*)\s*\{").unwrap();
+    let attribute_regex = Regex::new(r"(?:r#)?([\w-]+)\s*:\s*$").unwrap();
+    let strings = string_mask(element);
+    let opens = open_regex
+        .captures_iter(element)
+        .filter(|open| !strings[open.get(0).unwrap().start()])
+        .map(|open| {
+            let whole = open.get(0).unwrap();
+            (
+                open[1].to_string(),
+                whole.end() - 1,
+                block_end(element, whole.end() - 1),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    literal_regex
+        .captures_iter(element)
+        .map(|literal| {
+            let whole = literal.get(0).unwrap();
+            let tag = opens
+                .iter()
+                .filter(|(_, open, close)| *open < whole.start() && whole.end() <= *close)
+                .max_by_key(|(_, open, _)| *open)
+                .map(|(tag, _, _)| tag.clone())
+                .unwrap_or_default();
+            let attribute = attribute_regex
+                .captures(&element[..whole.start()])
+                .map(|attribute| attribute[1].to_string());
+            Literal {
+                start: whole.start(),
+                end: whole.end(),
+                value: literal[1].to_string(),
+                tag,
+                attribute,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn repeated_siblings_become_loops() {
+    let rsx = r#"div { class: "grid",
+    div { class: "card",
+        h2 { "Basic" }
+        p { class: "price", "$9" }
+    }
+    div { class: "card",
+        h2 { "Pro" }
+        p { class: "price", "$29" }
+    }
+    div { class: "card",
+        h2 { "Team" }
+        p { class: "price", "$99" }
+    }
+    ul {
+        li { "One" }
+        li { "Two" }
+    }
+}"#;
+    let (collapsed, lists) = collapse_repeated(rsx, "Pricing");
+    assert_eq!(
+        collapsed,
+        r#"div { class: "grid",
+    for card in pricing_cards.iter() {
+        div { class: "card",
+            h2 { "{card.title}" }
+            p { class: "price", "{card.description}" }
+        }
+    }
+    ul {
+        li { "One" }
+        li { "Two" }
+    }
+}"#
+    );
+    assert_eq!(
+        lists,
+        [DataList {
+            name: "PricingCard".to_string(),
+            fields: vec!["title".to_string(), "description".to_string()],
+            rows: vec![
+                vec!["Basic".to_string(), "$9".to_string()],
+                vec!["Pro".to_string(), "$29".to_string()],
+                vec!["Team".to_string(), "$99".to_string()],
+            ],
+        }]
+    );
+    assert_eq!(
+        lists[0].param(),
+        "#[props(default = PRICING_CARDS.to_vec())] pricing_cards: Vec<PricingCard>"
+    );
+
+    let code = crate::rsx_to_component("Pricing", "", rsx, &[], crate::DioxusVersion::V0_5);
+    assert!(code.starts_with("#[derive(Debug, Clone, Copy, PartialEq)]\npub struct PricingCard {\n    pub title: &'static str,\n    pub description: &'static str,\n}\n\nconst PRICING_CARDS: &[PricingCard] = &[\n    PricingCard { title: \"Basic\", description: \"$9\" },"));
+    assert!(code.contains("pub fn Pricing(#[props(default = PRICING_CARDS.to_vec())] pricing_cards: Vec<PricingCard>) -> Element {"));
+    assert_eq!(crate::validate::validate_code(&code), []);
+
+    // The app is launched without props, so its data is a local variable
+    let code = crate::write_component("app", "", rsx, &[], crate::DioxusVersion::V0_5, true);
+    assert!(code.contains("pub fn app() -> Element {\n    let app_cards = APP_CARDS;\n"));
+    assert_eq!(crate::validate::validate_code(&code), []);
+
+    // Only the whitespace before the run is its indent
+    let (collapsed, _) =
+        collapse_repeated(r#"ul { li { "One" } li { "Two" } li { "Three" } }"#, "Menu");
+    assert_eq!(
+        collapsed,
+        "ul { for list_item in menu_list_items.iter() {\n    li { \"{list_item.item}\" }\n} }"
+    );
+}
+
+#[test]
+fn interactive_siblings_are_not_loops() {
+    let rsx = r#"div {
+    div { class: "tabs",
+        button { "Profile" }
+        button { "Account" }
+        button { "Billing" }
+    }
+    label { input { r#type: "checkbox", name: "email_alerts" } "Email alerts" }
+    label { input { r#type: "checkbox", name: "sms_alerts" } "SMS alerts" }
+    label { input { r#type: "checkbox", name: "push_alerts" } "Push alerts" }
+    nav {
+        a { href: "/", "Home" }
+        a { href: "/about", "About" }
+        a { href: "/contact", "Contact" }
+    }
+}"#;
+    let (collapsed, lists) = collapse_repeated(rsx, "Settings");
+    assert_eq!(collapsed, rsx);
+    assert!(lists.is_empty());
+
+    let code = crate::rsx_to_component(
+        "Settings",
+        "",
+        rsx,
+        &["selected_tab".to_string()],
+        crate::DioxusVersion::V0_5,
+    );
+    assert!(code.contains("button { onclick: move |_| selected_tab.set(0), \"Profile\" }"));
+    assert!(code.contains("button { onclick: move |_| selected_tab.set(2), \"Billing\" }"));
+    for signal in ["email_alerts", "sms_alerts", "push_alerts"] {
+        assert!(code.contains(&format!("let mut {signal} = use_signal(|| false);")));
+    }
+    assert_eq!(crate::validate::validate_code(&code), []);
+}
@@ -0,0 +1,281 @@
+use crate::interactive::string_mask;
+use crate::PartialState;
+use convert_case::{Case, Casing};
+use regex::{Captures, Regex};
+
+// Strict and reserved keywords in the 2021 edition
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+
+// Names from the rust and dioxus preludes that a component would shadow
+const RESERVED_COMPONENT_NAMES: &[&str] = &[
+    "Element",
+    "Fragment",
+    "Props",
+    "Signal",
+    "Memo",
+    "Resource",
+    "Event",
+    "EventHandler",
+    "Callback",
+    "Link",
+    "Router",
+    "Outlet",
+    "Route",
+    "Routable",
+    "Component",
+    "VNode",
+    "Template",
+    "String",
+    "Option",
+    "Some",
+    "None",
+    "Result",
+    "Ok",
+    "Err",
+    "Vec",
+    "Box",
+    "Self",
+    "Crate",
+    "Super",
+];
+
+pub(crate) fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// The name as a rust identifier. Keywords are escaped like `r#type`
+pub(crate) fn rust_ident(name: &str) -> String {
+    if is_keyword(name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// A valid upper camel case component name. Names that would shadow a prelude item get a `Component` suffix. Names of HTML elements like `Footer` are kept, because component tags are marked before rosetta lowercases them
+pub(crate) fn component_name(name: &str) -> String {
+    let name = name
+        .replace(|c: char| !c.is_ascii_alphanumeric(), " ")
+        .to_case(Case::UpperCamel);
+    if name.is_empty() {
+        "Component".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("Component{name}")
+    } else if RESERVED_COMPONENT_NAMES.contains(&name.as_str()) {
+        format!("{name}Component")
+    } else {
+        name
+    }
+}
+
+/// A valid snake case prop or signal name. Keywords get a `_value` suffix, because placeholders are used in format strings where raw identifiers like `{r#type}` are not allowed
+pub(crate) fn prop_name(name: &str) -> String {
+    let name = name
+        .replace(|c: char| !c.is_ascii_alphanumeric(), " ")
+        .to_case(Case::Snake);
+    if name.is_empty() {
+        "value".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("value_{name}")
+    } else if is_keyword(&name) {
+        format!("{name}_value")
+    } else {
+        name
+    }
+}
+
+/// Rewrite every `{placeholder}` in the HTML to a valid prop name
+pub(crate) fn sanitize_placeholders(html: &str) -> String {
+    let placeholder_regex = Regex::new(r"\{\s*([A-Za-z_][\w-]*)\s*\}").unwrap();
+    placeholder_regex
+        .replace_all(html, |placeholder: &Captures| {
+            format!("{{{}}}", prop_name(&placeholder[1]))
+        })
+        .to_string()
+}
+
+/// Escape every use of the props and signals that are keywords outside of the strings in the rsx, like `checked: is()` or `type.set(...)`. Strings are left alone, because [`prop_name`] renames keyword placeholders
+pub(crate) fn escape_keywords<'a>(rsx: &str, names: impl IntoIterator<Item = &'a str>) -> String {
+    let mut rsx = rsx.to_string();
+    for name in names {
+        if !is_keyword(name) {
+            continue;
+        }
+        let name = regex::escape(name);
+        let strings = string_mask(&rsx);
+        let ident_regex = Regex::new(&format!(r"\b{name}\b")).unwrap();
+        let mut escaped = String::new();
+        let mut copied = 0;
+        for ident in ident_regex.find_iter(&rsx) {
+            let before = &rsx[..ident.start()];
+            let after = &rsx[ident.end()..];
+            let is_attribute =
+                after.trim_start().starts_with(':') && !after.trim_start().starts_with("::");
+            if strings[ident.start()] || before.ends_with("r#") || is_attribute {
+                continue;
+            }
+            escaped += &rsx[copied..ident.start()];
+            escaped += "r#";
+            copied = ident.start();
+        }
+        escaped += &rsx[copied..];
+        rsx = escaped;
+    }
+    rsx
+}
+
+impl PartialState {
+    /// Give every component a valid, unique upper camel case name and every placeholder a valid snake case name. References to renamed components in the HTML are rewritten to match.
+    pub(crate) fn sanitize_names(&mut self) {
+        let mut renamed = Vec::new();
+        let mut taken = Vec::<String>::new();
+        for component in &mut self.components {
+            let base = component_name(&component.name);
+            let mut name = base.clone();
+            let mut index = 2;
+            while taken.contains(&name) {
+                name = format!("{base}{index}");
+                index += 1;
+            }
+            // References in the HTML go to the first component with the name
+            if name != component.name && !renamed.iter().any(|(old, _)| *old == component.name) {
+                renamed.push((component.name.clone(), name.clone()));
+            }
+            taken.push(name.clone());
+            component.name = name;
+            component.state = component
+                .state
+                .iter()
+                .map(|state| prop_name(state))
+                .collect();
+        }
+
+        let rewrite = |html: &str| sanitize_placeholders(&rename_components(html, &renamed));
+        self.html = rewrite(&self.html);
+        for component in &mut self.components {
+            component.html = rewrite(&component.html);
+        }
+    }
+}
+
+// Rewrite the opening and closing tags of renamed components
+fn rename_components(html: &str, renamed: &[(String, String)]) -> String {
+    if renamed.is_empty() {
+        return html.to_string();
+    }
+    let tag_regex = Regex::new(r"<(/?)([A-Za-z][\w-]*)").unwrap();
+    tag_regex
+        .replace_all(html, |tag: &Captures| {
+            let name = renamed
+                .iter()
+                .find(|(old, _)| *old == tag[2])
+                .map_or(&tag[2], |(_, new)| new.as_str());
+            format!("<{}{name}", &tag[1])
+        })
+        .to_string()
+}
+
+#[tokio::test]
+async fn sanitize_generated_names() {
+    use crate::{ScriptedBackend, UiGenerator};
+
+    let backend = ScriptedBackend::new().with_response(
+        "A user list.
+components:
+- user-card: A card for a user
+- Element: A list element
+- UserCard: Another user card
+interactive:
+- UserCard: Self
+HTML:
+<div><user-card></user-card><Element/><UserCard/></div>
+component html:
+user-card:
+<div class=\"p-4\">{userName} {type} {self}</div>
+Element:
+<li>{ item-label }</li>
+UserCard:
+<p>{self}</p><|eot_id|>",
+    );
+    let state = UiGenerator::new(backend).generate("users").await.unwrap();
+    let components = state.components();
+    let names = components
+        .iter()
+        .map(|component| component.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["UserCard", "ElementComponent", "UserCard2"]);
+    assert_eq!(
+        state.html,
+        "<div><UserCard></UserCard><ElementComponent/><UserCard2/></div>"
+    );
+    assert_eq!(
+        components[0].html,
+        "<div class=\"p-4\">{user_name} {type_value} {self_value}</div>"
+    );
+    assert_eq!(components[1].html, "<li>{item_label}</li>");
+    assert_eq!(components[2].state, ["self_value"]);
+
+    assert_eq!(prop_name("type"), "type_value");
+    assert_eq!(prop_name("Loop"), "loop_value");
+    let rsx = r#"input { r#type: "text", value: "{type}", oninput: move |event| type.set(event.value()), "the type" }"#;
+    assert_eq!(
+        escape_keywords(rsx, ["type"]),
+        r#"input { r#type: "text", value: "{type}", oninput: move |event| r#type.set(event.value()), "the type" }"#
+    );
+}
+
+#[tokio::test]
+async fn components_named_like_elements() {
+    use crate::{ScriptedBackend, UiGenerator};
+
+    let backend = ScriptedBackend::new().with_response(
+        "A page with a footer.
+components:
+- Footer: The links at the bottom of the page
+- Button: A rounded button
+HTML:
+<main><footer class=\"text-sm\">Draft</footer><Button/><Footer/></main>
+component html:
+Footer:
+<footer class=\"border-t\"><p>Made in Lisbon</p></footer>
+Button:
+<button class=\"rounded\">Save</button><|eot_id|>",
+    );
+    let state = UiGenerator::new(backend)
+        .generate("a page with a footer")
+        .await
+        .unwrap();
+    let names = state
+        .components()
+        .iter()
+        .map(|component| component.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["Footer", "Button"]);
+
+    // The element and the component with the same name stay apart in the rsx
+    let app = state.app_component().unwrap();
+    assert!(app.contains("footer { class: \"text-sm\", \"Draft\" }"));
+    assert!(app.contains("Button {}"));
+    assert!(app.contains("Footer {}"));
+    let footer = state.components()[0]
+        .component_string_with(state.components())
+        .unwrap();
+    assert!(footer.contains("pub fn Footer() -> Element {"));
+    assert!(footer.contains("footer { class: \"border-t\","));
+    assert!(footer.contains("p { \"Made in Lisbon\" }"));
+    assert!(state.app_diagnostics().is_empty());
+    assert!(state
+        .components()
+        .iter()
+        .all(|component| component.is_valid()));
+
+    assert!(state.preview().contains(
+        "<main><footer class=\"text-sm\">Draft</footer><button class=\"rounded\">Save</button><footer class=\"border-t\"><p>Made in Lisbon</p></footer></main>"
+    ));
+}
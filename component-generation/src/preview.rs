@@ -0,0 +1,809 @@
+use crate::props::{type_from_name, BOOLEAN_ATTRIBUTES, NUMERIC_ATTRIBUTES};
+use crate::slots::SLOT_SUFFIX;
+use crate::validate::{attribute_end, next_brace, rsx_macro};
+use crate::{PartialState, PropType};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use regex::{Captures, Regex};
+use std::path::Path;
+use syn::{Expr, FnArg, Item, Lit, Meta, Pat, Stmt, Type};
+
+// Components that render each other in a loop stop after this many levels
+const MAX_DEPTH: usize = 16;
+
+// Elements without a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+// Attributes that are written in camel case in HTML but in snake case in rsx
+const CAMEL_CASE_ATTRIBUTES: &[&str] = &[
+    "viewBox",
+    "preserveAspectRatio",
+    "gradientUnits",
+    "gradientTransform",
+    "patternUnits",
+    "clipPathUnits",
+];
+
+impl PartialState {
+    /// Render the app with every component to a static HTML page styled with the Tailwind CDN. The page is rendered from the same rsx as the code: props the app doesn't pass get sample values, signals show their initial value and event handlers are left out, so the UI can be previewed in a browser without compiling it.
+    pub fn preview(&self) -> String {
+        let body = match self.app_component() {
+            Ok(app) => {
+                let mut functions = parse_functions(&app);
+                for component in &self.components {
+                    if let Ok(code) = component.component_string_with(&self.components) {
+                        functions.extend(parse_functions(&code));
+                    }
+                }
+                Renderer {
+                    functions: &functions,
+                }
+                .component("app", Vec::new(), 0)
+            }
+            Err(err) => format!("<!-- {} -->", escape(&err.to_string())),
+        };
+        let title = self.description.lines().next().unwrap_or_default();
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{}</title>
+<script src="https://cdn.tailwindcss.com"></script>
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+            escape(title.trim())
+        )
+    }
+
+    /// Write the [`PartialState::preview`] of the UI to a file
+    pub fn write_preview(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.preview())
+    }
+}
+
+// A value in the rsx while it renders
+#[derive(Debug, Clone)]
+enum Value {
+    Text(String),
+    Html(String),
+    // The rows of a list, with the value of each field
+    Rows(Vec<Row>),
+    Row(Row),
+}
+
+type Row = Vec<(String, String)>;
+
+// A component in the generated code
+struct Function {
+    name: String,
+    // The value of each prop if the call doesn't pass it
+    props: Vec<(String, Value)>,
+    // Signals and lists bound before the rsx
+    locals: Vec<(String, Value)>,
+    rsx: TokenStream,
+}
+
+// The components in the code, with the lists in its constants
+fn parse_functions(code: &str) -> Vec<Function> {
+    let Ok(file) = syn::parse_file(code) else {
+        return Vec::new();
+    };
+    let lists = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Const(constant) => Some((constant.ident.to_string(), rows(&constant.expr))),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let list = |name: &str| {
+        lists
+            .iter()
+            .find(|(list, _)| list == name)
+            .map(|(_, rows)| Value::Rows(rows.clone()))
+    };
+
+    let mut functions = Vec::new();
+    for item in &file.items {
+        let Item::Fn(function) = item else {
+            continue;
+        };
+        let Some(rsx) = function.block.stmts.iter().find_map(rsx_macro) else {
+            continue;
+        };
+        let mut props = Vec::new();
+        for input in &function.sig.inputs {
+            let FnArg::Typed(input) = input else {
+                continue;
+            };
+            let Pat::Ident(pat) = &*input.pat else {
+                continue;
+            };
+            let name = pat.ident.to_string();
+            // `#[props(default = PRICING_CARDS.to_vec())]`
+            let default = input.attrs.iter().find_map(|attr| match &attr.meta {
+                Meta::List(attr) => attr
+                    .tokens
+                    .clone()
+                    .into_iter()
+                    .find_map(|token| match token {
+                        TokenTree::Ident(ident) => list(&ident.to_string()),
+                        _ => None,
+                    }),
+                _ => None,
+            });
+            let ty = match &*input.ty {
+                Type::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident.to_string()),
+                _ => None,
+            };
+            let value = match (default, ty.as_deref()) {
+                (Some(rows), _) => rows,
+                (None, Some("Element")) => Value::Html(String::new()),
+                (None, Some("Vec")) => Value::Rows(Vec::new()),
+                (None, Some(ty)) => {
+                    let ty = match ty {
+                        "String" => PropType::String,
+                        "bool" => PropType::Bool,
+                        "i64" | "i32" | "u64" | "u32" | "usize" => PropType::Integer,
+                        "f64" | "f32" => PropType::Float,
+                        _ => continue,
+                    };
+                    Value::Text(typed_sample_value(&name, ty, None))
+                }
+                (None, None) => continue,
+            };
+            props.push((name, value));
+        }
+
+        let mut locals = Vec::new();
+        for stmt in &function.block.stmts {
+            let Stmt::Local(local) = stmt else {
+                continue;
+            };
+            let (Pat::Ident(pat), Some(init)) = (&local.pat, &local.init) else {
+                continue;
+            };
+            let value = match &*init.expr {
+                // `use_signal(|| 0)`, or `use_state(cx, || 0)` in dioxus 0.4
+                Expr::Call(call) => match call.args.last() {
+                    Some(Expr::Closure(closure)) => literal(&closure.body).map(Value::Text),
+                    // `use_signal(String::new)`
+                    Some(Expr::Path(_)) => Some(Value::Text(String::new())),
+                    _ => None,
+                },
+                // `let app_cards = APP_CARDS;`
+                Expr::Path(path) => path
+                    .path
+                    .get_ident()
+                    .and_then(|ident| list(&ident.to_string())),
+                _ => None,
+            };
+            if let Some(value) = value {
+                locals.push((pat.ident.to_string(), value));
+            }
+        }
+
+        functions.push(Function {
+            name: function.sig.ident.to_string(),
+            props,
+            locals,
+            rsx: rsx.tokens.clone(),
+        });
+    }
+    functions
+}
+
+// The rows of a list constant like `&[Card { title: "Basic" }, ...]`
+fn rows(expr: &Expr) -> Vec<Row> {
+    let Expr::Reference(reference) = expr else {
+        return Vec::new();
+    };
+    let Expr::Array(array) = &*reference.expr else {
+        return Vec::new();
+    };
+    array
+        .elems
+        .iter()
+        .filter_map(|row| match row {
+            Expr::Struct(row) => Some(
+                row.fields
+                    .iter()
+                    .filter_map(|field| match &field.member {
+                        syn::Member::Named(name) => Some((name.to_string(), literal(&field.expr)?)),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+// The text of a literal expression
+fn literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr) => lit_text(&expr.lit),
+        _ => None,
+    }
+}
+
+fn lit_text(lit: &Lit) -> Option<String> {
+    match lit {
+        Lit::Str(lit) => Some(lit.value()),
+        Lit::Int(lit) => Some(lit.base10_digits().to_string()),
+        Lit::Float(lit) => lit
+            .base10_parse::<f64>()
+            .ok()
+            .map(|value| value.to_string()),
+        Lit::Bool(lit) => Some(lit.value.to_string()),
+        _ => None,
+    }
+}
+
+struct Renderer<'a> {
+    functions: &'a [Function],
+}
+
+impl Renderer<'_> {
+    // Render a component with the props passed to it
+    fn component(&self, name: &str, passed: Vec<(String, Value)>, depth: usize) -> String {
+        let Some(function) = self.functions.iter().find(|function| function.name == name) else {
+            return String::new();
+        };
+        if depth > MAX_DEPTH {
+            return String::new();
+        }
+        let mut bindings = function
+            .props
+            .iter()
+            .map(|(prop, default)| {
+                let value = passed
+                    .iter()
+                    .find(|(name, _)| name == prop)
+                    .map_or(default, |(_, value)| value);
+                (prop.clone(), value.clone())
+            })
+            .collect::<Vec<_>>();
+        bindings.extend(function.locals.iter().cloned());
+        self.nodes(function.rsx.clone(), &bindings, depth)
+    }
+
+    // Render the nodes in the body of an `rsx!` call or an element. Attributes are skipped.
+    fn nodes(&self, body: TokenStream, bindings: &[(String, Value)], depth: usize) -> String {
+        let tokens = body.into_iter().collect::<Vec<_>>();
+        let mut html = String::new();
+        let mut index = 0;
+        while index < tokens.len() {
+            if is_attribute(&tokens, index) {
+                index = attribute_end(&tokens, index);
+                continue;
+            }
+            let name = match &tokens[index] {
+                TokenTree::Literal(literal) => {
+                    if let Some(text) = string(literal) {
+                        html += &escape(&interpolate(&text, bindings));
+                    }
+                    index += 1;
+                    continue;
+                }
+                // `{children}`
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    let expr = group.stream().into_iter().collect::<Vec<_>>();
+                    match self.eval(&expr, bindings, depth) {
+                        Some(Value::Html(inner)) => html += &inner,
+                        Some(Value::Text(text)) => html += &escape(&text),
+                        _ => {}
+                    }
+                    index += 1;
+                    continue;
+                }
+                TokenTree::Ident(ident) => ident.to_string(),
+                _ => {
+                    index += 1;
+                    continue;
+                }
+            };
+            let Some(body) = next_brace(&tokens, index) else {
+                break;
+            };
+            let TokenTree::Group(group) = &tokens[body] else {
+                unreachable!()
+            };
+            match name.as_str() {
+                "for" => html += &self.for_loop(&tokens[index + 1..body], group, bindings, depth),
+                "if" => {
+                    let (branch, end) = self.condition(&tokens, index, bindings, depth);
+                    html += &branch;
+                    index = end;
+                    continue;
+                }
+                "Link" => {
+                    html += &format!(
+                        "<a href=\"#\">{}</a>",
+                        self.nodes(group.stream(), bindings, depth)
+                    )
+                }
+                _ if name.starts_with(|c: char| c.is_ascii_lowercase()) => {
+                    html += &self.element(&name, group.stream(), bindings, depth)
+                }
+                _ => {
+                    let mut passed = self.attributes(group.stream(), bindings, depth);
+                    passed.push((
+                        "children".to_string(),
+                        Value::Html(self.nodes(group.stream(), bindings, depth)),
+                    ));
+                    html += &self.component(&name, passed, depth + 1);
+                }
+            }
+            index = body + 1;
+        }
+        html
+    }
+
+    fn element(
+        &self,
+        tag: &str,
+        body: TokenStream,
+        bindings: &[(String, Value)],
+        depth: usize,
+    ) -> String {
+        let mut html = format!("<{tag}");
+        for (name, value) in self.attributes(body.clone(), bindings, depth) {
+            let Value::Text(value) = value else {
+                continue;
+            };
+            let name = html_attribute(&name);
+            match BOOLEAN_ATTRIBUTES.contains(&name.as_str()) {
+                // `disabled="false"` would still disable the element
+                true if value == "false" => {}
+                true => html += &format!(" {name}"),
+                false => html += &format!(" {name}=\"{}\"", escape(&value).replace('"', "&quot;")),
+            }
+        }
+        html += ">";
+        if !VOID_ELEMENTS.contains(&tag) {
+            html += &self.nodes(body, bindings, depth);
+            html += &format!("</{tag}>");
+        }
+        html
+    }
+
+    // The attributes of an element or the props of a call. Attributes that can't be evaluated, like event handlers, are left out.
+    fn attributes(
+        &self,
+        body: TokenStream,
+        bindings: &[(String, Value)],
+        depth: usize,
+    ) -> Vec<(String, Value)> {
+        let tokens = body.into_iter().collect::<Vec<_>>();
+        let mut attributes = Vec::new();
+        let mut index = 0;
+        while index < tokens.len() {
+            if !is_attribute(&tokens, index) {
+                index += 1;
+                continue;
+            }
+            let end = attribute_end(&tokens, index);
+            let value_end = match &tokens[end - 1] {
+                TokenTree::Punct(punct) if punct.as_char() == ',' => end - 1,
+                _ => end,
+            };
+            let name = match &tokens[index] {
+                TokenTree::Literal(literal) => string(literal),
+                token => Some(token.to_string().trim_start_matches("r#").to_string()),
+            };
+            if let (Some(name), Some(value)) = (
+                name.filter(|name| name != "prevent_default"),
+                self.eval(&tokens[index + 2..value_end], bindings, depth),
+            ) {
+                attributes.push((name, value));
+            }
+            index = end;
+        }
+        attributes
+    }
+
+    // Render the body of `for card in cards.iter() { ... }` for each row of the list
+    fn for_loop(
+        &self,
+        head: &[TokenTree],
+        body: &proc_macro2::Group,
+        bindings: &[(String, Value)],
+        depth: usize,
+    ) -> String {
+        let [TokenTree::Ident(row), TokenTree::Ident(keyword), list @ ..] = head else {
+            return String::new();
+        };
+        let Some(Value::Rows(rows)) = self.eval(list, bindings, depth).filter(|_| keyword == "in")
+        else {
+            return String::new();
+        };
+        let mut html = String::new();
+        for value in rows {
+            let mut bindings = bindings.to_vec();
+            bindings.push((row.to_string(), Value::Row(value)));
+            html += &self.nodes(body.stream(), &bindings, depth);
+        }
+        html
+    }
+
+    // Render the branch of the `if` at the index whose condition holds. Returns the HTML and the index after the last `else`
+    fn condition(
+        &self,
+        tokens: &[TokenTree],
+        index: usize,
+        bindings: &[(String, Value)],
+        depth: usize,
+    ) -> (String, usize) {
+        let mut html = None;
+        let mut index = index;
+        loop {
+            let Some(body) = next_brace(tokens, index) else {
+                return (html.unwrap_or_default(), tokens.len());
+            };
+            let TokenTree::Group(group) = &tokens[body] else {
+                unreachable!()
+            };
+            // Conditions the preview can't evaluate render their branch
+            let holds = !matches!(
+                self.eval(&tokens[index + 1..body], bindings, depth),
+                Some(Value::Text(value)) if value == "false"
+            );
+            if html.is_none() && holds {
+                html = Some(self.nodes(group.stream(), bindings, depth));
+            }
+            let after = body + 1;
+            if !matches!(tokens.get(after), Some(TokenTree::Ident(ident)) if ident == "else") {
+                return (html.unwrap_or_default(), after);
+            }
+            match tokens.get(after + 1) {
+                Some(TokenTree::Ident(ident)) if ident == "if" => index = after + 1,
+                Some(TokenTree::Group(group)) => {
+                    let html = html.unwrap_or_else(|| self.nodes(group.stream(), bindings, depth));
+                    return (html, after + 2);
+                }
+                _ => return (html.unwrap_or_default(), after + 1),
+            }
+        }
+    }
+
+    // The value of an expression in the rsx, or `None` if the preview can't evaluate it
+    fn eval(
+        &self,
+        expr: &[TokenTree],
+        bindings: &[(String, Value)],
+        depth: usize,
+    ) -> Option<Value> {
+        let lookup = |name: &proc_macro2::Ident| {
+            bindings
+                .iter()
+                .rev()
+                .find(|(binding, _)| name == binding)
+                .map(|(_, value)| value.clone())
+        };
+        match expr {
+            [TokenTree::Literal(literal)] => match Lit::new(literal.clone()) {
+                Lit::Str(lit) => Some(Value::Text(interpolate(&lit.value(), bindings))),
+                lit => lit_text(&lit).map(Value::Text),
+            },
+            [TokenTree::Ident(ident)] if ident == "true" || ident == "false" => {
+                Some(Value::Text(ident.to_string()))
+            }
+            [TokenTree::Ident(ident)] => lookup(ident),
+            // Reading a signal like `selected_tab()`, or iterating over a list like `cards.iter()`
+            [TokenTree::Ident(ident), TokenTree::Group(args)]
+            | [TokenTree::Ident(ident), TokenTree::Punct(_), TokenTree::Ident(_), TokenTree::Group(args)]
+                if args.stream().is_empty() =>
+            {
+                lookup(ident)
+            }
+            // `*selected_tab.get()` in dioxus 0.4
+            [TokenTree::Punct(star), TokenTree::Ident(ident), TokenTree::Punct(_), TokenTree::Ident(get), TokenTree::Group(_)]
+                if star.as_char() == '*' && get == "get" =>
+            {
+                lookup(ident)
+            }
+            // A field of a row like `card.title`
+            [TokenTree::Ident(ident), TokenTree::Punct(dot), TokenTree::Ident(field)]
+                if dot.as_char() == '.' =>
+            {
+                match lookup(ident)? {
+                    Value::Row(row) => row
+                        .into_iter()
+                        .find(|(name, _)| field == name)
+                        .map(|(_, value)| Value::Text(value)),
+                    _ => None,
+                }
+            }
+            [TokenTree::Punct(not), rest @ ..] if not.as_char() == '!' => {
+                match self.eval(rest, bindings, depth)? {
+                    Value::Text(value) => Some(Value::Text((value == "false").to_string())),
+                    _ => None,
+                }
+            }
+            [TokenTree::Ident(mac), TokenTree::Punct(bang), TokenTree::Group(body)]
+                if mac == "rsx" && bang.as_char() == '!' =>
+            {
+                Some(Value::Html(self.nodes(body.stream(), bindings, depth)))
+            }
+            // `cx.render(rsx! { ... })` in dioxus 0.4
+            [TokenTree::Ident(_), TokenTree::Punct(_), TokenTree::Ident(render), TokenTree::Group(args)]
+                if render == "render" =>
+            {
+                let args = args.stream().into_iter().collect::<Vec<_>>();
+                self.eval(&args, bindings, depth)
+            }
+            _ => None,
+        }
+    }
+}
+
+// An identifier or string followed by a single colon starts an attribute like `class: "p-4"` or `"data-id": "1"`
+fn is_attribute(tokens: &[TokenTree], index: usize) -> bool {
+    matches!(&tokens[index], TokenTree::Ident(_) | TokenTree::Literal(_))
+        && matches!(
+            tokens.get(index + 1),
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':' && punct.spacing() == Spacing::Alone
+        )
+}
+
+// The value of a string literal
+fn string(literal: &proc_macro2::Literal) -> Option<String> {
+    match Lit::new(literal.clone()) {
+        Lit::Str(lit) => Some(lit.value()),
+        _ => None,
+    }
+}
+
+// The HTML name of an rsx attribute. `aria_pressed` becomes `aria-pressed` and `view_box` becomes `viewBox`
+fn html_attribute(name: &str) -> String {
+    match CAMEL_CASE_ATTRIBUTES
+        .iter()
+        .find(|attribute| attribute.to_lowercase() == name.replace('_', ""))
+    {
+        Some(attribute) => attribute.to_string(),
+        None => name.replace('_', "-"),
+    }
+}
+
+// Fill the `{name}` and `{card.title}` arguments of a format string
+fn interpolate(text: &str, bindings: &[(String, Value)]) -> String {
+    let mut output = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let argument = chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+                let argument = argument.split(':').next().unwrap_or_default().trim();
+                let (name, field) = match argument.split_once('.') {
+                    Some((name, field)) => (name, Some(field)),
+                    None => (argument, None),
+                };
+                let value = bindings.iter().rev().find(|(binding, _)| binding == name);
+                match (value, field) {
+                    (Some((_, Value::Text(value) | Value::Html(value))), None) => output += value,
+                    (Some((_, Value::Row(row))), Some(field)) => {
+                        if let Some((_, value)) = row.iter().find(|(name, _)| name == field) {
+                            output += value;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/// Fill every placeholder in the HTML with the value passed for it, or a sample value
+pub(crate) fn fill_placeholders(html: &str, values: &[(String, String)]) -> String {
+    let attribute_regex = Regex::new(r#"(\s+)([\w:-]+)="([^"]*)""#).unwrap();
+    let placeholder_regex = Regex::new(r"\{([a-z_][a-z0-9_]*)\}").unwrap();
+    let value = |name: &str, attribute: Option<&str>| {
+        values
+            .iter()
+            .find(|(value, _)| value == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| sample_value(name, attribute))
+    };
+
+    // Placeholders in attributes are filled first because the attribute changes the sample value
+    let html = attribute_regex.replace_all(html, |attribute: &Captures| {
+        let name = &attribute[2];
+        let filled = placeholder_regex.replace_all(&attribute[3], |placeholder: &Captures| {
+            value(&placeholder[1], Some(name))
+        });
+        // `disabled="false"` would still disable the element
+        if BOOLEAN_ATTRIBUTES.contains(&name) && filled == "false" {
+            return String::new();
+        }
+        format!("{}{name}=\"{filled}\"", &attribute[1])
+    });
+    placeholder_regex
+        .replace_all(&html, |placeholder: &Captures| value(&placeholder[1], None))
+        .to_string()
+}
+
+/// A realistic value for a placeholder based on its name and the attribute it is used in
+pub(crate) fn sample_value(name: &str, attribute: Option<&str>) -> String {
+    let ty = match attribute {
+        _ if name == "children" || name.ends_with(SLOT_SUFFIX) => PropType::Element,
+        Some(attribute) if BOOLEAN_ATTRIBUTES.contains(&attribute) => PropType::Bool,
+        Some(attribute) if NUMERIC_ATTRIBUTES.contains(&attribute) => PropType::Integer,
+        _ => type_from_name(name),
+    };
+    typed_sample_value(name, ty, attribute)
+}
+
+/// A realistic value for a prop of a type. Elements and event handlers have no sample value and return an empty string.
+pub(crate) fn typed_sample_value(name: &str, ty: PropType, attribute: Option<&str>) -> String {
+    let words = name
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let has_word = |candidates: &[&str]| words.iter().any(|word| candidates.contains(word));
+    let last = words.last().copied().unwrap_or_default();
+
+    match ty {
+        PropType::Element | PropType::MouseEventHandler | PropType::FormEventHandler => {
+            return String::new()
+        }
+        PropType::Bool => return (!has_word(&["disabled", "hidden"])).to_string(),
+        PropType::Integer if last == "year" => return "2024".to_string(),
+        PropType::Integer if last == "age" => return "32".to_string(),
+        PropType::Integer => return "3".to_string(),
+        PropType::Float if has_word(&["rating", "score"]) => return "4.5".to_string(),
+        PropType::Float if has_word(&["percent", "percentage", "progress"]) => {
+            return "60".to_string()
+        }
+        PropType::Float => return "19.99".to_string(),
+        PropType::String => {}
+    }
+
+    let image = has_word(&[
+        "image",
+        "img",
+        "avatar",
+        "photo",
+        "picture",
+        "logo",
+        "thumbnail",
+        "cover",
+        "banner",
+    ]);
+    match attribute {
+        Some("href" | "action") => return "#".to_string(),
+        Some("src" | "poster") if has_word(&["avatar", "profile"]) => {
+            return "https://placehold.co/128x128".to_string()
+        }
+        Some("src" | "poster") => return "https://placehold.co/600x400".to_string(),
+        _ if image && has_word(&["url", "src"]) => {
+            return "https://placehold.co/600x400".to_string()
+        }
+        _ if has_word(&["url", "link", "href"]) => return "#".to_string(),
+        _ => {}
+    }
+    if has_word(&["email"]) {
+        "jane@example.com".to_string()
+    } else if has_word(&["phone"]) {
+        "(555) 123-4567".to_string()
+    } else if has_word(&["date"]) {
+        "March 14, 2024".to_string()
+    } else if has_word(&["author", "username", "user"]) || last == "name" {
+        "Jane Doe".to_string()
+    } else {
+        // "product_title" becomes "Product title"
+        let text = words.join(" ");
+        let mut chars = text.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[tokio::test]
+async fn preview_renders_components() {
+    use crate::{ScriptedBackend, UiGenerator};
+
+    let backend = ScriptedBackend::new().with_response(
+        "A team page.
+components:
+- Card: A card with a title
+- Avatar: A round profile picture
+HTML:
+<div class=\"grid\"><Card title=\"Our team\"><Avatar/><Avatar/></Card></div>
+component html:
+Card:
+<section><h2>{title}</h2>{children}<p>{card_description}</p></section>
+Avatar:
+<img class=\"rounded-full\" src=\"{avatar_url}\" alt=\"{user_name}\"><button disabled=\"{is_disabled}\">{follower_count}</button><|eot_id|>",
+    );
+    let state = UiGenerator::new(backend)
+        .generate("a team page")
+        .await
+        .unwrap();
+    let preview = state.preview();
+    // The card gets the title the app passes and sample values for the props the app leaves out
+    let avatar = "<img alt=\"Jane Doe\" class=\"rounded-full\" src=\"https://placehold.co/600x400\"><button>3</button>";
+    assert!(preview.contains(&format!(
+        "<div class=\"grid\"><section><h2>Our team</h2>{avatar}{avatar}<p>Card description</p></section></div>"
+    )));
+    assert!(preview.contains("<title>A team page.</title>"));
+
+    let backend = ScriptedBackend::new().with_response(
+        "A page with a layout.
+components:
+- Layout: A page with a header
+HTML:
+<Layout><nav slot=\"header\" class=\"flex\">Home</nav><p>Body</p></Layout>
+component html:
+Layout:
+<div><header>{header_slot}</header><main>{children}</main></div><|eot_id|>",
+    );
+    let state = UiGenerator::new(backend)
+        .generate("a page with a layout")
+        .await
+        .unwrap();
+    assert!(state.preview().contains(
+        "<div><header><nav class=\"flex\">Home</nav></header><main><p>Body</p></main></div>"
+    ));
+}
+
+#[tokio::test]
+async fn preview_renders_the_converted_rsx() {
+    use crate::{ScriptedBackend, UiGenerator};
+
+    let backend = ScriptedBackend::new().with_response(
+        "A newsletter signup.
+components:
+- Signup: An email field with tabs for the plan
+- Plans: The plans to pick from
+interactive:
+- Signup: selected_tab
+HTML:
+<Signup/><Plans/>
+component html:
+Signup:
+<form><input type=\"email\"><button>Weekly</button><button>Monthly</button><p>Plan {selected_tab}</p></form>
+Plans:
+<ul><li class=\"p-2\">Basic</li><li class=\"p-2\">Pro</li><li class=\"p-2\">Team</li></ul><|eot_id|>",
+    );
+    let mut state = UiGenerator::new(backend)
+        .generate("a newsletter signup")
+        .await
+        .unwrap();
+    // Signals show their initial value, event handlers are left out and lists render each row
+    let body = "<form><input value=\"\" type=\"email\"><button>Weekly</button><button>Monthly</button><p>Plan 0</p></form><ul><li class=\"p-2\">Basic</li><li class=\"p-2\">Pro</li><li class=\"p-2\">Team</li></ul>";
+    assert!(state.preview().contains(body));
+
+    state.set_target(crate::DioxusVersion::V0_4);
+    assert!(state.preview().contains(body));
+}
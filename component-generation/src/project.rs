@@ -0,0 +1,335 @@
+use crate::naming::rust_ident;
+use crate::{DioxusVersion, GenerationError, PartialState};
+use convert_case::{Case, Casing};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// A cargo project that runs a generated UI with `dx serve`
+#[derive(Debug, Clone)]
+pub struct Project {
+    pub name: String,
+    files: Vec<ProjectFile>,
+}
+
+/// A file in a generated project
+#[derive(Debug, Clone)]
+pub struct ProjectFile {
+    /// The path of the file relative to the root of the project
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+impl Project {
+    pub fn files(&self) -> &[ProjectFile] {
+        &self.files
+    }
+
+    /// Write every file in the project into the directory. Existing files are overwritten.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        for file in &self.files {
+            let path = dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, &file.contents)?;
+        }
+        Ok(())
+    }
+
+    fn add_file(&mut self, path: impl Into<PathBuf>, contents: impl Into<String>) {
+        self.files.push(ProjectFile {
+            path: path.into(),
+            contents: contents.into(),
+        });
+    }
+}
+
+impl PartialState {
+    /// Create a cargo project that launches the generated UI. Each component is written to its own file in the `components` module.
+    pub fn project(&self, name: &str) -> Result<Project, GenerationError> {
+        self.project_with_app(name, PartialState::app_component)
+    }
+
+    // Create a project with every component and the `app` component written by `app`
+    pub(crate) fn project_with_app(
+        &self,
+        name: &str,
+        app: impl FnOnce(&PartialState) -> Result<String, GenerationError>,
+    ) -> Result<Project, GenerationError> {
+        // Invalid classes would not be styled by the stylesheet
+        let mut state = self.clone();
+        state.fix_classes();
+        let name = package_name(name);
+        let mut project = Project {
+            name: name.clone(),
+            files: Vec::new(),
+        };
+
+        // The components of a site link between its pages with the router
+        let router = state
+            .components
+            .iter()
+            .any(|component| !component.links.is_empty());
+        project.add_file("Cargo.toml", cargo_toml(&name, self.target, router));
+        // The router is a separate crate in dioxus 0.4
+        let router_import = match (self.target, router) {
+            (DioxusVersion::V0_4, true) => "use dioxus_router::prelude::*;\n",
+            _ => "",
+        };
+        project.add_file("Dioxus.toml", dioxus_toml(&name));
+        project.add_file("tailwind.config.js", tailwind_config(&state.classes()));
+        project.add_file("input.css", input_css(&state.classes()));
+        project.add_file(".gitignore", GITIGNORE);
+        project.add_file("README.md", readme(&name, &self.description));
+
+        let main = format!(
+            "#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+{}
+mod components;
+#[allow(unused_imports)]
+use components::*;
+
+fn main() {{
+    {}
+}}
+{}
+",
+            router_import,
+            self.target.launch(),
+            app(&state)?
+        );
+        project.add_file("src/main.rs", main);
+
+        let mut components_module = String::new();
+        for component in &state.components {
+            let module = component.name.to_case(Case::Snake);
+            let ident = rust_ident(&module);
+            components_module += &format!("mod {ident};\npub use {ident}::*;\n");
+
+            let mut file = String::from("use dioxus::prelude::*;\n");
+            // Components may render other components
+            let uses_components = state.components.iter().any(|other| {
+                other.name != component.name && component.html.contains(&format!("<{}", other.name))
+            });
+            if uses_components {
+                file += "use super::*;\n";
+            }
+            let code = component.component_string_with(&state.components)?;
+            if code.contains("Route::") {
+                file += router_import;
+                file += "use crate::Route;\n";
+            }
+            file += &code;
+            file.push('\n');
+            project.add_file(format!("src/components/{module}.rs"), file);
+        }
+        project.add_file("src/components/mod.rs", components_module);
+
+        Ok(project)
+    }
+}
+
+// Turn any text into a valid cargo package name
+fn package_name(name: &str) -> String {
+    let name = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-");
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        Some(_) => format!("app-{name}"),
+        None => "app".to_string(),
+    }
+}
+
+fn cargo_toml(name: &str, target: DioxusVersion, router: bool) -> String {
+    let version = target.dependency();
+    let dependencies = match (target, router) {
+        // Dioxus 0.4 launches the app from the renderer crate
+        (DioxusVersion::V0_4, false) => {
+            format!("dioxus = \"{version}\"\ndioxus-web = \"{version}\"\n")
+        }
+        (DioxusVersion::V0_4, true) => format!(
+            "dioxus = \"{version}\"\ndioxus-web = \"{version}\"\ndioxus-router = \"{version}\"\n"
+        ),
+        (DioxusVersion::V0_5, false) => {
+            format!("dioxus = {{ version = \"{version}\", features = [\"web\"] }}\n")
+        }
+        (DioxusVersion::V0_5, true) => {
+            format!("dioxus = {{ version = \"{version}\", features = [\"web\", \"router\"] }}\n")
+        }
+    };
+    format!(
+        r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+{dependencies}"#
+    )
+}
+
+fn dioxus_toml(name: &str) -> String {
+    format!(
+        r#"[application]
+name = "{name}"
+default_platform = "web"
+out_dir = "dist"
+asset_dir = "assets"
+
+[web.app]
+title = "{name}"
+
+[web.watcher]
+reload_html = true
+watch_path = ["src", "assets"]
+
+[web.resource]
+# Build this stylesheet with `npx tailwindcss -i ./input.css -o ./assets/tailwind.css`
+style = ["/tailwind.css"]
+script = []
+
+[web.resource.dev]
+# Tailwind's play CDN styles the UI during development without building the stylesheet
+script = ["https://cdn.tailwindcss.com"]
+"#
+    )
+}
+
+fn readme(name: &str, description: &str) -> String {
+    format!(
+        "# {name}
+
+{description}
+
+Run the app with:
+
+```sh
+dx serve
+```
+
+Build the Tailwind stylesheet for a release build with:
+
+```sh
+npx tailwindcss -i ./input.css -o ./assets/tailwind.css
+```
+"
+    )
+}
+
+// The classes the UI uses are safelisted so the stylesheet includes them even if the code changes
+fn tailwind_config(classes: &BTreeSet<String>) -> String {
+    let safelist = classes
+        .iter()
+        .map(|class| format!("    {class:?},\n"))
+        .collect::<String>();
+    format!(
+        r#"/** @type {{import('tailwindcss').Config}} */
+module.exports = {{
+  mode: "all",
+  content: ["./src/**/*.{{rs,html,css}}", "./dist/**/*.html", "./input.css"],
+  safelist: [
+{safelist}  ],
+  theme: {{
+    extend: {{}},
+  }},
+  plugins: [],
+}};
+"#
+    )
+}
+
+// The stylesheet lists the classes the UI uses. Tailwind scans it for classes, so they are built even without the safelist
+fn input_css(classes: &BTreeSet<String>) -> String {
+    let classes = classes
+        .iter()
+        .map(|class| format!("  {class}\n"))
+        .collect::<String>();
+    format!(
+        "@tailwind base;
+@tailwind components;
+@tailwind utilities;
+
+/* The classes used by the generated UI:
+{classes}*/
+"
+    )
+}
+
+const GITIGNORE: &str = "/target
+/dist
+/assets/tailwind.css
+";
+
+#[test]
+fn package_names() {
+    assert_eq!(package_name("My landing page!"), "my-landing-page");
+    assert_eq!(package_name("3d viewer"), "app-3d-viewer");
+    assert_eq!(package_name("???"), "app");
+}
+
+#[test]
+fn stylesheet_lists_classes() {
+    let classes = BTreeSet::from(["flex".to_string(), "md:p-4".to_string()]);
+    let css = input_css(&classes);
+    assert!(css.starts_with("@tailwind base;"));
+    assert!(css.contains("\n  flex\n  md:p-4\n*/"));
+    assert!(tailwind_config(&classes).contains(r#""./input.css""#));
+}
+
+#[tokio::test]
+async fn main_calls_the_components() {
+    use crate::{ScriptedBackend, UiGenerator};
+
+    let backend = ScriptedBackend::new().with_response(
+        "A landing page.
+components:
+- NavBar: A navigation bar
+- Hero: A large heading with a call to action
+- Footer: The page footer
+HTML:
+<div><NavBar>{children}</NavBar><Hero/><Footer/></div>
+component html:
+NavBar:
+<nav class=\"flex\">{children}</nav>
+Hero:
+<section><h1>{title}</h1><a href=\"{cta_url}\">{cta_text}</a><span>{rating}</span></section>
+Footer:
+<footer class=\"p-4\">{copyright}</footer><|eot_id|>",
+    );
+    let state = UiGenerator::new(backend)
+        .generate("a landing page")
+        .await
+        .unwrap();
+    let project = state.project("landing").unwrap();
+    let main = project
+        .files()
+        .iter()
+        .find(|file| file.path == Path::new("src/main.rs"))
+        .unwrap();
+
+    let file = syn::parse_file(&main.contents).unwrap();
+    let app = file
+        .items
+        .iter()
+        .find_map(|item| match item {
+            syn::Item::Fn(function) if function.sig.ident == "app" => Some(function),
+            _ => None,
+        })
+        .unwrap();
+    assert!(app.sig.inputs.is_empty());
+    assert!(main.contents.contains("NavBar {}"));
+    assert!(main
+        .contents
+        .contains(r##"Hero { title: "Title", cta_url: "#", cta_text: "Cta text", rating: 4.5 }"##));
+    assert!(main
+        .contents
+        .contains(r#"Footer { copyright: "Copyright" }"#));
+    assert!(!main.contents.contains("footer {}"));
+}
@@ -0,0 +1,388 @@
+use crate::naming::{prop_name, rust_ident};
+use crate::slots::SLOT_SUFFIX;
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use syn::Lit;
+
+/// A property of a generated component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prop {
+    pub name: String,
+    pub ty: PropType,
+    /// Optional props are marked with `#[props(default)]`
+    pub optional: bool,
+}
+
+impl Display for Prop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.optional {
+            write!(f, "#[props(default)] ")?;
+        }
+        write!(f, "{}: {}", rust_ident(&self.name), self.ty)
+    }
+}
+
+/// The rust type of a component property
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PropType {
+    String,
+    Integer,
+    Float,
+    Bool,
+    Element,
+    /// An `EventHandler<MouseEvent>` for a click handler
+    MouseEventHandler,
+    /// An `EventHandler<FormEvent>` for a form or input handler
+    FormEventHandler,
+}
+
+impl Display for PropType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ty = match self {
+            PropType::String => "String",
+            PropType::Integer => "i64",
+            PropType::Float => "f64",
+            PropType::Bool => "bool",
+            PropType::Element => "Element",
+            PropType::MouseEventHandler => "EventHandler<MouseEvent>",
+            PropType::FormEventHandler => "EventHandler<FormEvent>",
+        };
+        write!(f, "{ty}")
+    }
+}
+
+pub(crate) const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "disabled",
+    "checked",
+    "selected",
+    "hidden",
+    "required",
+    "readonly",
+    "multiple",
+    "open",
+    "autofocus",
+];
+
+/// Give boolean attributes without a value like `<input required>` the value `"true"`. Rosetta would convert them to `required: "false"`.
+pub(crate) fn expand_boolean_attributes(html: &str) -> String {
+    let tag_regex = Regex::new(r#"<[A-Za-z][\w-]*((?:\s+[\w:.-]+(?:="[^"]*")?)*)\s*/?>"#).unwrap();
+    let attribute_regex = Regex::new(r#"(\s+)([\w:.-]+)(="[^"]*")?"#).unwrap();
+    tag_regex
+        .replace_all(html, |tag: &Captures| {
+            let whole = tag.get(0).unwrap();
+            let attributes = tag.get(1).unwrap();
+            let expanded =
+                attribute_regex.replace_all(attributes.as_str(), |attribute: &Captures| {
+                    match attribute.get(3) {
+                        None if BOOLEAN_ATTRIBUTES.contains(&&attribute[2]) => {
+                            format!("{}{}=\"true\"", &attribute[1], &attribute[2])
+                        }
+                        _ => attribute[0].to_string(),
+                    }
+                });
+            format!(
+                "{}{expanded}{}",
+                &html[whole.start()..attributes.start()],
+                &html[attributes.end()..whole.end()]
+            )
+        })
+        .to_string()
+}
+
+pub(crate) const NUMERIC_ATTRIBUTES: &[&str] = &[
+    "width",
+    "height",
+    "min",
+    "max",
+    "step",
+    "rows",
+    "cols",
+    "colspan",
+    "rowspan",
+    "tabindex",
+    "maxlength",
+    "minlength",
+    "size",
+];
+
+const URL_ATTRIBUTES: &[&str] = &["href", "src", "action", "poster"];
+
+// Attributes that only change how an element looks or is described. A prop that is only used in these attributes can be left out.
+const DECORATIVE_ATTRIBUTES: &[&str] = &["class", "style", "title", "alt", "placeholder", "id"];
+
+const BOOLEAN_PREFIXES: &[&str] = &["is", "has", "show", "can", "should", "enable"];
+
+const BOOLEAN_WORDS: &[&str] = &[
+    "disabled", "enabled", "checked", "selected", "active", "open", "visible", "hidden",
+    "required", "verified",
+];
+
+const INTEGER_WORDS: &[&str] = &[
+    "count",
+    "number",
+    "quantity",
+    "age",
+    "year",
+    "rank",
+    "views",
+    "likes",
+    "followers",
+    "following",
+    "downloads",
+    "stars",
+    "votes",
+];
+
+const FLOAT_WORDS: &[&str] = &[
+    "price",
+    "rating",
+    "score",
+    "amount",
+    "cost",
+    "total",
+    "balance",
+    "percent",
+    "percentage",
+    "progress",
+];
+
+// Where a placeholder appears in the rsx
+struct Usage {
+    // The attribute the placeholder is used in, or None if it is used in text
+    attribute: Option<String>,
+    // If the placeholder is the whole value of the string
+    whole: bool,
+}
+
+/// Find every `{placeholder}` in the rsx and infer the type of the prop from the placeholder name and where it is used. Placeholders are found in text nodes, attribute values and expressions like `{children}`, and their names are converted to snake case. Props are returned in the order they first appear.
+pub(crate) fn infer_props(rsx: &str) -> Vec<Prop> {
+    let mut usages: Vec<(String, Vec<Usage>)> = Vec::new();
+    if let Ok(tokens) = rsx.parse::<TokenStream>() {
+        find_usages(tokens, &mut usages);
+    }
+
+    usages
+        .into_iter()
+        .map(|(name, usages)| infer_prop(name, &usages))
+        .collect()
+}
+
+// Walk the rsx tree and record every placeholder with the attribute it is assigned to, if there is one
+fn find_usages(tokens: TokenStream, usages: &mut Vec<(String, Vec<Usage>)>) {
+    let placeholder_regex = Regex::new(r"\{\s*([A-Za-z_][\w-]*)\s*(?::[^{}]*)?\}").unwrap();
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut attribute = None;
+    let mut index = 0;
+    while index < tokens.len() {
+        // `name:` or `"name":` starts an attribute or a component field. `::` is part of a path
+        let starts_attribute = matches!(
+            tokens.get(index + 1),
+            Some(TokenTree::Punct(colon)) if colon.as_char() == ':' && colon.spacing() == Spacing::Alone
+        ) && !matches!(
+            index.checked_sub(1).map(|before| &tokens[before]),
+            Some(TokenTree::Punct(colon)) if colon.as_char() == ':'
+        );
+        match &tokens[index] {
+            TokenTree::Ident(ident) if starts_attribute => {
+                let name = ident.to_string();
+                attribute = Some(name.trim_start_matches("r#").to_lowercase());
+                index += 2;
+                continue;
+            }
+            TokenTree::Literal(literal) => {
+                let Lit::Str(value) = Lit::new(literal.clone()) else {
+                    attribute = None;
+                    index += 1;
+                    continue;
+                };
+                let value = value.value();
+                if starts_attribute {
+                    attribute = Some(value.to_lowercase());
+                    index += 2;
+                    continue;
+                }
+                // Escaped braces are not placeholders
+                let value = value.replace("{{", "  ").replace("}}", "  ");
+                for placeholder in placeholder_regex.captures_iter(&value) {
+                    let usage = Usage {
+                        attribute: attribute.clone(),
+                        whole: placeholder.get(0).unwrap().as_str() == value.trim(),
+                    };
+                    add_usage(usages, &placeholder[1], usage);
+                }
+            }
+            TokenTree::Group(group) => {
+                let inner = group.stream().into_iter().collect::<Vec<_>>();
+                match inner.as_slice() {
+                    // An expression like `{children}` in the body of an element
+                    [TokenTree::Ident(ident)]
+                        if attribute.is_none() && group.delimiter() == Delimiter::Brace =>
+                    {
+                        let usage = Usage {
+                            attribute: None,
+                            whole: true,
+                        };
+                        add_usage(usages, &ident.to_string(), usage);
+                    }
+                    _ => find_usages(group.stream(), usages),
+                }
+            }
+            _ => {}
+        }
+        attribute = None;
+        index += 1;
+    }
+}
+
+fn add_usage(usages: &mut Vec<(String, Vec<Usage>)>, name: &str, usage: Usage) {
+    let name = prop_name(name);
+    match usages.iter_mut().find(|(existing, _)| *existing == name) {
+        Some((_, existing)) => existing.push(usage),
+        None => usages.push((name, vec![usage])),
+    }
+}
+
+fn infer_prop(name: String, usages: &[Usage]) -> Prop {
+    if name == "children" {
+        return Prop {
+            name,
+            ty: PropType::Element,
+            optional: false,
+        };
+    }
+
+    // Slots are only filled with elements when they are the whole text of an element
+    if let Some(slot) = name.strip_suffix(SLOT_SUFFIX) {
+        if !slot.is_empty()
+            && usages
+                .iter()
+                .all(|usage| usage.attribute.is_none() && usage.whole)
+        {
+            return Prop {
+                name: slot.to_string(),
+                ty: PropType::Element,
+                optional: true,
+            };
+        }
+    }
+
+    let mut ty = None;
+    for usage in usages {
+        let Some(attribute) = &usage.attribute else {
+            continue;
+        };
+        if URL_ATTRIBUTES.contains(&attribute.as_str()) {
+            ty = Some(PropType::String);
+            break;
+        }
+        if usage.whole {
+            if BOOLEAN_ATTRIBUTES.contains(&attribute.as_str()) {
+                ty = Some(PropType::Bool);
+            } else if NUMERIC_ATTRIBUTES.contains(&attribute.as_str()) {
+                ty = Some(PropType::Integer);
+            }
+        }
+    }
+    let ty = ty.unwrap_or_else(|| type_from_name(&name));
+
+    let only_decorative = usages.iter().all(|usage| {
+        usage.attribute.as_deref().is_some_and(|attribute| {
+            DECORATIVE_ATTRIBUTES.contains(&attribute) || attribute.starts_with("aria")
+        })
+    });
+    let optional = ty == PropType::Bool || only_decorative;
+
+    Prop { name, ty, optional }
+}
+
+pub(crate) fn type_from_name(name: &str) -> PropType {
+    let words = name
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let (Some(first), Some(last)) = (words.first(), words.last()) else {
+        return PropType::String;
+    };
+
+    if (words.len() > 1 && BOOLEAN_PREFIXES.contains(first)) || BOOLEAN_WORDS.contains(last) {
+        PropType::Bool
+    } else if FLOAT_WORDS.contains(last) {
+        PropType::Float
+    } else if INTEGER_WORDS.contains(last) || *first == "num" {
+        PropType::Integer
+    } else {
+        PropType::String
+    }
+}
+
+/// Turn attributes that are set to a single non-string placeholder into expressions. `disabled: "{is_disabled}"` becomes `disabled: is_disabled`
+pub(crate) fn unquote_typed_attributes(rsx: &str, props: &[Prop]) -> String {
+    let mut rsx = rsx.to_string();
+    for prop in props {
+        if !matches!(
+            prop.ty,
+            PropType::Integer | PropType::Float | PropType::Bool
+        ) {
+            continue;
+        }
+        let attribute_regex = Regex::new(&format!(
+            r#"(["\w-]+\s*:\s*)"\{{{}\}}""#,
+            regex::escape(&prop.name)
+        ))
+        .unwrap();
+        rsx = attribute_regex
+            .replace_all(&rsx, format!("${{1}}{}", prop.name))
+            .to_string();
+    }
+    rsx
+}
+
+#[test]
+fn infer_prop_types() {
+    let rsx = r#"
+div { class: "{card_class}",
+    a { href: "{profile_count}", "{user_name}" }
+    p { "{download_count} downloads, rated {rating}" }
+    input { r#type: "checkbox", disabled: "{locked}", maxlength: "{max_chars}" }
+    span { "Verified: {is_verified}" }
+    Badge { label: "{badgeLabel} ({badgeCount})" }
+    {children}
+}"#;
+    let props = infer_props(rsx);
+    let types = props
+        .iter()
+        .map(|prop| (prop.name.as_str(), prop.ty, prop.optional))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        [
+            ("card_class", PropType::String, true),
+            ("profile_count", PropType::String, false),
+            ("user_name", PropType::String, false),
+            ("download_count", PropType::Integer, false),
+            ("rating", PropType::Float, false),
+            ("locked", PropType::Bool, true),
+            ("max_chars", PropType::Integer, false),
+            ("is_verified", PropType::Bool, true),
+            ("badge_label", PropType::String, false),
+            ("badge_count", PropType::Integer, false),
+            ("children", PropType::Element, false),
+        ]
+    );
+
+    let unquoted = unquote_typed_attributes(rsx, &props);
+    assert!(unquoted.contains("disabled: locked,"));
+    assert!(unquoted.contains("maxlength: max_chars }"));
+    assert!(unquoted.contains("\"{download_count} downloads, rated {rating}\""));
+}
+
+#[test]
+fn boolean_attributes_without_values() {
+    let html = r#"<form><input required class="border" title="disabled"><button disabled>Send</button><input type="checkbox" checked/></form>"#;
+    assert_eq!(
+        expand_boolean_attributes(html),
+        r#"<form><input required="true" class="border" title="disabled"><button disabled="true">Send</button><input type="checkbox" checked="true"/></form>"#
+    );
+}
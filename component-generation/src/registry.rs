@@ -0,0 +1,136 @@
+use crate::{Component, GenerationError, Prop};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A persistent set of accepted components that are reused across generations.
+///
+/// Components in the registry are offered to the model as components that already exist. When a generated UI declares a component with the same name, the HTML from the registry is used instead of generating new HTML.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComponentRegistry {
+    components: Vec<RegisteredComponent>,
+}
+
+/// A component that was accepted into a [`ComponentRegistry`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegisteredComponent {
+    pub name: String,
+    pub description: String,
+    pub html: String,
+    /// The state from the interactive section of the component
+    #[serde(default)]
+    pub state: Vec<String>,
+    pub props: Vec<Prop>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a registry from a JSON file. Returns an empty registry if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+
+    /// Save the registry to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+
+    /// Accept a component into the registry. A component with the same name is replaced.
+    pub fn insert(&mut self, component: &Component) -> Result<(), GenerationError> {
+        let registered = RegisteredComponent {
+            name: component.name.clone(),
+            description: component.description.clone(),
+            html: component.html.clone(),
+            state: component.state.clone(),
+            props: component.props()?,
+        };
+        match self
+            .components
+            .iter_mut()
+            .find(|existing| existing.name == registered.name)
+        {
+            Some(existing) => *existing = registered,
+            None => self.components.push(registered),
+        }
+        Ok(())
+    }
+
+    /// Remove a component from the registry
+    pub fn remove(&mut self, name: &str) -> Option<RegisteredComponent> {
+        let index = self
+            .components
+            .iter()
+            .position(|component| component.name == name)?;
+        Some(self.components.remove(index))
+    }
+
+    /// Find a component by name. Names are compared without case.
+    pub fn get(&self, name: &str) -> Option<&RegisteredComponent> {
+        self.components
+            .iter()
+            .find(|component| component.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn components(&self) -> &[RegisteredComponent] {
+        &self.components
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+#[tokio::test]
+async fn reuse_registered_components() {
+    use crate::{PropType, ScriptedBackend, UiGenerator};
+
+    let mut registry = ComponentRegistry::new();
+    registry.components.push(RegisteredComponent {
+        name: "Button".to_string(),
+        description: "A rounded button".to_string(),
+        html: "<button class=\"rounded px-4\">{label}</button>".to_string(),
+        state: Vec::new(),
+        props: vec![Prop {
+            name: "label".to_string(),
+            ty: PropType::String,
+            optional: false,
+        }],
+    });
+
+    let backend = ScriptedBackend::new()
+        .with_response(
+            "A sign up form.
+components:
+- SignUpForm: A form to create an account
+- Button: A submit button
+HTML:
+<div><SignUpForm/></div>
+",
+        )
+        .with_response("<form><input type=\"email\"/><Button/></form>\n");
+    let state = UiGenerator::new(backend)
+        .with_registry(registry)
+        .generate("a sign up page")
+        .await
+        .unwrap();
+
+    let components = state.components();
+    assert_eq!(
+        components[0].html,
+        "<form><input type=\"email\"/><Button/></form>"
+    );
+    assert_eq!(components[1].description, "A rounded button");
+    assert_eq!(
+        components[1].html,
+        "<button class=\"rounded px-4\">{label}</button>"
+    );
+}
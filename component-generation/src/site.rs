@@ -0,0 +1,603 @@
+use crate::interactive::{block_end, string_mask, top_level_attributes};
+use crate::preview::fill_placeholders;
+use crate::{
+    check_complete, Component, DioxusVersion, GenerationError, GenerationEvent, KalosmBackend,
+    ModelBackend, OutputGrammar, PartialState, Project, UiGenerator,
+};
+use convert_case::{Case, Casing};
+use futures_util::StreamExt;
+use regex::Regex;
+
+/// Generate a site with several pages for the description with the default model.
+pub async fn generate_site(description: &str) -> Result<Site, GenerationError> {
+    UiGenerator::new(KalosmBackend::default_model().await?)
+        .generate_site(description)
+        .await
+}
+
+/// A site with several pages that share components. The pages are connected with a dioxus-router `Route` enum.
+#[derive(Debug, Clone)]
+pub struct Site {
+    /// The description the site was generated from
+    pub description: String,
+    pages: Vec<Page>,
+    target: DioxusVersion,
+}
+
+/// A page of a [`Site`]
+#[derive(Debug, Clone)]
+pub struct Page {
+    /// The name of the page component and its route like `PricingPage`
+    pub name: String,
+    /// The path of the route like `/pricing`. The first page is the root of the site.
+    pub path: String,
+    pub description: String,
+    /// The UI of the page. Components with the same name on different pages are the same component.
+    pub state: PartialState,
+}
+
+impl<B: ModelBackend> UiGenerator<B> {
+    /// Generate a site with several pages for the description.
+    ///
+    /// The model first lists the pages of the site, then each page is generated like a UI. Components from earlier pages are offered to later pages, so a nav bar or footer is only generated once.
+    pub async fn generate_site(&self, description: &str) -> Result<Site, GenerationError> {
+        let description = description.trim().to_string();
+        let grammar = OutputGrammar::pages().with_chat_template(self.chat_template);
+        let raw_output = self
+            .backend
+            .stream_structured_text(
+                &self.prompt(
+                    &format!("{description}\n\nList the pages of this website."),
+                    "\npages:\n",
+                ),
+                &grammar,
+            )
+            .collect::<String>()
+            .await;
+        check_complete(&grammar, &raw_output)?;
+        let plan = parse_pages(&raw_output)?;
+        let paths = plan
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| page_path(index, name))
+            .collect::<Vec<_>>();
+
+        let mut pages: Vec<Page> = Vec::new();
+        for ((name, page_description), path) in plan.into_iter().zip(paths.iter().cloned()) {
+            let shared = shared_components(&pages);
+            let mut prompt = format!(
+                "{description}\n\nThe {} page: {page_description}\n\nLink to the pages of the site with these paths:\n",
+                name.to_case(Case::Lower)
+            );
+            for path in &paths {
+                prompt += &format!("- {path}\n");
+            }
+            let new_shared = shared
+                .iter()
+                .filter(|component| self.registry.get(&component.name).is_none())
+                .collect::<Vec<_>>();
+            if !new_shared.is_empty() {
+                prompt += "\nThese components were made for other pages of the site. Reuse them by name where they fit:\n";
+                for component in new_shared {
+                    prompt += &format!("- {}: {}\n", component.name, component.description);
+                }
+            }
+
+            let mut state = PartialState::new(prompt, self.target);
+            {
+                let mut sections = Box::pin(self.stream_sections(
+                    &mut state,
+                    "\nDESCRIPTION:\n",
+                    &OutputGrammar::sections().with_chat_template(self.chat_template),
+                ));
+                while let Some(event) = sections.next().await {
+                    event?;
+                }
+            }
+            {
+                let mut html = Box::pin(self.stream_component_html(&mut state, &shared));
+                while let Some(event) = html.next().await {
+                    event?;
+                }
+            }
+            let mut finish = Box::pin(self.finish(state));
+            let state = loop {
+                match finish.next().await {
+                    Some(Ok(GenerationEvent::Finished(state))) => break state,
+                    Some(_) => {}
+                    None => unreachable!("the event stream always ends with a finished event"),
+                }
+            };
+
+            pages.push(Page {
+                name: page_name(&name),
+                path,
+                description: page_description,
+                state,
+            });
+        }
+
+        Ok(Site {
+            description,
+            pages,
+            target: self.target,
+        })
+    }
+}
+
+impl Site {
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    /// The version of dioxus the code is written for
+    pub fn target(&self) -> DioxusVersion {
+        self.target
+    }
+
+    /// Write the code of the site for another version of dioxus
+    pub fn set_target(&mut self, target: DioxusVersion) {
+        self.target = target;
+        for page in &mut self.pages {
+            page.state.set_target(target);
+        }
+    }
+
+    /// Every component used by the pages. Links to other pages are rendered as router links.
+    pub fn components(&self) -> Vec<Component> {
+        shared_components(&self.pages)
+            .into_iter()
+            .map(|mut component| {
+                component.links = self.links();
+                component
+            })
+            .collect()
+    }
+
+    /// The component for a page. Components rendered by the layout are removed from the page. The router renders pages without props, so placeholders in the page are filled with sample values.
+    pub fn page_component(&self, page: &Page) -> Component {
+        let (header, footer) = self.layout_components();
+        let mut html = fill_placeholders(&page.state.html, &[]);
+        for name in header.iter().chain(&footer) {
+            html = element_regex(name).replace_all(&html, "").to_string();
+        }
+        Component {
+            name: page.name.clone(),
+            description: page.description.clone(),
+            html,
+            state: Vec::new(),
+            diagnostics: Vec::new(),
+            target: self.target,
+            links: self.links(),
+        }
+    }
+
+    /// The `Route` enum with a route for every page
+    pub fn routes(&self) -> String {
+        let (header, footer) = self.layout_components();
+        let mut routes = String::from(
+            "#[derive(Clone, Debug, PartialEq, Routable)]\n#[rustfmt::skip]\npub enum Route {\n",
+        );
+        let indent = if header.is_empty() && footer.is_empty() {
+            "    "
+        } else {
+            routes += &format!("    #[layout({})]\n", self.layout_name());
+            "        "
+        };
+        for page in &self.pages {
+            routes += &format!(
+                "{indent}#[route({:?})]\n{indent}{} {{}},\n",
+                page.path, page.name
+            );
+        }
+        routes += "}";
+        routes
+    }
+
+    /// The layout around every page with the components that are rendered on every page like a nav bar or footer. Returns `None` if no component is rendered on every page.
+    pub fn layout(&self) -> Option<String> {
+        let (header, footer) = self.layout_components();
+        if header.is_empty() && footer.is_empty() {
+            return None;
+        }
+        let mut rsx = String::new();
+        for name in &header {
+            rsx += &format!("\n        {name} {{}}");
+        }
+        rsx += "\n        Outlet::<Route> {}";
+        for name in &footer {
+            rsx += &format!("\n        {name} {{}}");
+        }
+        Some(format!(
+            "/// The layout around every page\n#[component]\n{} {{\n    {}\n}}",
+            self.target.signature(&self.layout_name(), &[], &[]),
+            self.target.render(&rsx)
+        ))
+    }
+
+    /// The `app` component that renders the page for the current route
+    pub fn app_component(&self) -> String {
+        format!(
+            "/// Renders the page for the current route\n#[component]\n{} {{\n    {}\n}}",
+            self.target.signature("app", &[], &[]),
+            self.target.render("\n        Router::<Route> {}")
+        )
+    }
+
+    /// Create a cargo project that launches the site. Each page and component is written to its own file in the `components` module.
+    pub fn project(&self, name: &str) -> Result<Project, GenerationError> {
+        let mut state = PartialState::new(self.description.clone(), self.target);
+        state.description = self.description.clone();
+        state.components = self.components();
+        state
+            .components
+            .extend(self.pages.iter().map(|page| self.page_component(page)));
+        state.project_with_app(name, |_| {
+            let mut app = self.routes();
+            if let Some(layout) = self.layout() {
+                app += "\n\n";
+                app += &layout;
+            }
+            app += "\n\n";
+            app += &self.app_component();
+            Ok(app)
+        })
+    }
+
+    // The path and route of every page
+    fn links(&self) -> Vec<(String, String)> {
+        self.pages
+            .iter()
+            .map(|page| (page.path.clone(), format!("Route::{} {{}}", page.name)))
+            .collect()
+    }
+
+    // The components rendered without props on every page, split into the ones before and after the content of the page
+    fn layout_components(&self) -> (Vec<String>, Vec<String>) {
+        let Some(first) = self.pages.first() else {
+            return (Vec::new(), Vec::new());
+        };
+        let mut layout = shared_components(&self.pages)
+            .into_iter()
+            .filter(|component| {
+                self.pages
+                    .iter()
+                    .all(|page| element_regex(&component.name).is_match(&page.state.html))
+            })
+            .map(|component| {
+                let start = element_regex(&component.name)
+                    .find(&first.state.html)
+                    .unwrap()
+                    .start();
+                (component.name, start)
+            })
+            .collect::<Vec<_>>();
+        layout.sort_by_key(|(_, start)| *start);
+
+        // A component is before the content if only opening tags and other layout components come before it
+        let opening_tags = Regex::new(r"^(?:\s*<[a-z][\w-]*(?:\s[^<>]*[^/<>])?>)*\s*$").unwrap();
+        let (header, footer): (Vec<_>, Vec<_>) = layout.iter().partition(|(_, start)| {
+            let mut before = first.state.html[..*start].to_string();
+            for (name, _) in &layout {
+                before = element_regex(name).replace_all(&before, "").to_string();
+            }
+            opening_tags.is_match(&before)
+        });
+        let names = |components: Vec<&(String, usize)>| {
+            components
+                .into_iter()
+                .map(|(name, _)| name.clone())
+                .collect()
+        };
+        (names(header), names(footer))
+    }
+
+    // `Layout`, unless a component already has that name
+    fn layout_name(&self) -> String {
+        let taken = |name: &str| {
+            self.pages.iter().any(|page| {
+                page.name == name
+                    || page
+                        .state
+                        .components
+                        .iter()
+                        .any(|component| component.name == name)
+            })
+        };
+        match taken("Layout") {
+            true => "SiteLayout".to_string(),
+            false => "Layout".to_string(),
+        }
+    }
+}
+
+/// Render links to the paths as router links. `a { href: "/pricing", "Pricing" }` becomes `Link { to: Route::PricingPage {}, "Pricing" }`
+pub(crate) fn link_routes(code: &str, links: &[(String, String)]) -> String {
+    if links.is_empty() {
+        return code.to_string();
+    }
+    let anchor_regex = Regex::new(r"\ba\s*\{").unwrap();
+    let href_regex = Regex::new(r#"\bhref\s*:\s*"[^"]*"\s*,?"#).unwrap();
+    let strings = string_mask(code);
+    let mut output = String::new();
+    let mut copied = 0;
+    for anchor in anchor_regex.find_iter(code) {
+        if strings[anchor.start()] || anchor.start() < copied {
+            continue;
+        }
+        let open = anchor.end() - 1;
+        let close = block_end(code, open);
+        if close >= code.len() {
+            continue;
+        }
+        let body = &code[open + 1..close];
+        let (attributes, _) = top_level_attributes(body);
+        let Some(route) = attributes
+            .iter()
+            .find(|(attribute, _)| attribute == "href")
+            .and_then(|(_, href)| {
+                links
+                    .iter()
+                    .find(|(path, _)| same_path(path, href))
+                    .map(|(_, route)| route)
+            })
+        else {
+            continue;
+        };
+        // Attributes come before children, so the first href is the href of the link
+        let Some(href) = href_regex
+            .find_iter(body)
+            .find(|href| !strings[open + 1 + href.start()])
+        else {
+            continue;
+        };
+        output += &code[copied..anchor.start()];
+        output += &format!(
+            "Link {{{}to: {route},{}}}",
+            &body[..href.start()],
+            &body[href.end()..]
+        );
+        copied = close + 1;
+    }
+    output += &code[copied..];
+    output
+}
+
+// Paths match without a trailing slash and ignoring case
+fn same_path(path: &str, href: &str) -> bool {
+    href.starts_with('/')
+        && path
+            .trim_end_matches('/')
+            .eq_ignore_ascii_case(href.trim_end_matches('/'))
+}
+
+// A component rendered without props or children like `<NavBar/>`
+fn element_regex(name: &str) -> Regex {
+    let name = regex::escape(name);
+    Regex::new(&format!(r"<{name}\s*/>|<{name}\s*>\s*</{name}\s*>")).unwrap()
+}
+
+// Every component of the pages. Components with the same name are shared, so only the first one is kept
+fn shared_components(pages: &[Page]) -> Vec<Component> {
+    let mut components: Vec<Component> = Vec::new();
+    for component in pages.iter().flat_map(|page| &page.state.components) {
+        if !components.iter().any(|other| other.name == component.name) {
+            components.push(component.clone());
+        }
+    }
+    components
+}
+
+// The name and description of each page in the page list
+fn parse_pages(raw_output: &str) -> Result<Vec<(String, String)>, GenerationError> {
+    let pages = raw_output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (name, description) = line
+                .trim()
+                .strip_prefix('-')
+                .and_then(|line| line.split_once(':'))
+                .ok_or_else(|| GenerationError::MalformedPage {
+                    line: line.to_string(),
+                    raw_output: raw_output.to_string(),
+                })?;
+            Ok((name.trim().to_string(), description.trim().to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if pages.is_empty() {
+        return Err(GenerationError::NoPages {
+            raw_output: raw_output.to_string(),
+        });
+    }
+    Ok(pages)
+}
+
+// `Pricing` becomes `PricingPage` so pages don't clash with components
+fn page_name(name: &str) -> String {
+    match name.ends_with("Page") {
+        true => name.to_string(),
+        false => format!("{name}Page"),
+    }
+}
+
+// The first page is the root of the site. `AboutUs` becomes `/about-us`
+fn page_path(index: usize, name: &str) -> String {
+    match index {
+        0 => "/".to_string(),
+        _ => format!(
+            "/{}",
+            name.strip_suffix("Page")
+                .unwrap_or(name)
+                .to_case(Case::Kebab)
+        ),
+    }
+}
+
+#[tokio::test]
+async fn generate_site_with_shared_layout() {
+    use crate::ScriptedBackend;
+
+    let backend = ScriptedBackend::new()
+        .with_response("- Home: The landing page of the bakery\n- Menu: The breads and cakes\n\n")
+        .with_response(
+            "The landing page of a bakery.
+components:
+- NavBar: The site navigation
+- Footer: The site footer
+HTML:
+<div><NavBar/><main><h1>Fresh bread</h1><a href=\"/menu\">See the menu</a></main><Footer/></div>
+",
+        )
+        .with_response(
+            "<nav class=\"flex\"><a href=\"/\">Home</a><a href=\"/menu\">Menu</a></nav>\n",
+        )
+        .with_response("<footer class=\"p-4\">Bakery</footer>\n")
+        .with_response(
+            "The menu of a bakery.
+components:
+- NavBar: The site navigation
+- MenuItem: A bread or cake
+- Footer: The site footer
+HTML:
+<div><NavBar/><section><MenuItem/></section><Footer/></div>
+",
+        )
+        .with_response("<div class=\"rounded\">{name}</div>\n");
+    let site = UiGenerator::new(backend)
+        .generate_site("a bakery")
+        .await
+        .unwrap();
+
+    let pages = site.pages();
+    assert_eq!(pages.len(), 2);
+    assert_eq!(
+        (pages[0].name.as_str(), pages[0].path.as_str()),
+        ("HomePage", "/")
+    );
+    assert_eq!(
+        (pages[1].name.as_str(), pages[1].path.as_str()),
+        ("MenuPage", "/menu")
+    );
+    // The nav bar is generated once and shared with the menu page
+    let components = site.components();
+    assert_eq!(
+        components
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>(),
+        ["NavBar", "Footer", "MenuItem"]
+    );
+    assert_eq!(
+        pages[1].state.components[0].html,
+        "<nav class=\"flex\"><a href=\"/\">Home</a><a href=\"/menu\">Menu</a></nav>"
+    );
+
+    assert_eq!(
+        site.routes(),
+        "#[derive(Clone, Debug, PartialEq, Routable)]
+#[rustfmt::skip]
+pub enum Route {
+    #[layout(Layout)]
+        #[route(\"/\")]
+        HomePage {},
+        #[route(\"/menu\")]
+        MenuPage {},
+}"
+    );
+    let layout = site.layout().unwrap();
+    assert!(layout.contains(
+        "pub fn Layout() -> Element {
+    rsx! {
+        NavBar {}
+        Outlet::<Route> {}
+        Footer {}
+    }
+}"
+    ));
+    assert_eq!(crate::validate::validate_code(&layout), []);
+    assert_eq!(
+        site.page_component(&pages[1]).html,
+        "<div><section><MenuItem/></section></div>"
+    );
+
+    let links = site.page_component(&pages[0]).links;
+    assert_eq!(
+        link_routes(
+            r#"nav { class: "flex",
+    a { href: "/", "Home" }
+    a { class: "underline", href: "/menu/",
+        span { "Menu" }
+    }
+    a { href: "https://example.com", "Elsewhere" }
+}"#,
+            &links
+        ),
+        r#"nav { class: "flex",
+    Link { to: Route::HomePage {}, "Home" }
+    Link { class: "underline", to: Route::MenuPage {},
+        span { "Menu" }
+    }
+    a { href: "https://example.com", "Elsewhere" }
+}"#
+    );
+}
+
+#[tokio::test]
+async fn route_links_in_a_three_page_nav() {
+    use crate::ScriptedBackend;
+
+    let backend = ScriptedBackend::new()
+        .with_response(
+            "- Home: The landing page\n- Menu: The breads and cakes\n- Contact: How to find us\n\n",
+        )
+        .with_response(
+            "The landing page of a bakery.
+components:
+- NavBar: The site navigation
+HTML:
+<div><NavBar/><h1>{headline}</h1></div>
+",
+        )
+        .with_response(
+            "<nav class=\"flex\"><a href=\"/\">Home</a><a href=\"/menu\">Menu</a><a href=\"/contact\">Contact</a></nav>\n",
+        )
+        .with_response(
+            "The menu of a bakery.
+components:
+- NavBar: The site navigation
+HTML:
+<div><NavBar/><p>Bread</p></div>
+",
+        )
+        .with_response(
+            "The contact page of a bakery.
+components:
+- NavBar: The site navigation
+HTML:
+<div><NavBar/><p>Call us</p></div>
+",
+        );
+    let site = UiGenerator::new(backend)
+        .generate_site("a bakery")
+        .await
+        .unwrap();
+
+    // The links are not collapsed into a loop, so each one becomes a router link
+    let nav = site.components()[0].component_string().unwrap();
+    assert!(!nav.contains("for "));
+    for link in [
+        "Link { to: Route::HomePage {}, \"Home\" }",
+        "Link { to: Route::MenuPage {}, \"Menu\" }",
+        "Link { to: Route::ContactPage {}, \"Contact\" }",
+    ] {
+        assert!(nav.contains(link), "{link}");
+    }
+
+    // The routes render pages without props
+    let home = site.page_component(&site.pages()[0]);
+    assert_eq!(home.html, "<div><h1>Headline</h1></div>");
+    let code = home.component_string().unwrap();
+    assert!(code.contains("pub fn HomePage() -> Element {"));
+    assert_eq!(crate::validate::validate_code(&code), []);
+}
@@ -0,0 +1,126 @@
+use crate::interactive::{block_end, string_mask, top_level_attributes};
+use crate::naming::prop_name;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Placeholders that end with this suffix are slots. `{header_slot}` becomes an `Element` prop named `header`
+pub(crate) const SLOT_SUFFIX: &str = "_slot";
+
+static COMPONENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[A-Z][A-Za-z0-9_]*\s*\{").unwrap());
+static ELEMENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[A-Za-z][A-Za-z0-9_]*\s*\{").unwrap());
+static SLOT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bslot\s*:\s*"[^"]*"\s*,?\s*"#).unwrap());
+
+/// Move the children of components that fill a slot into the slot prop. `Layout { nav { slot: "header", "Home" } }` becomes `Layout { header: rsx! { nav { "Home" } }, }`
+pub(crate) fn fill_slots(rsx: &str) -> String {
+    let strings = string_mask(rsx);
+    let mut output = String::new();
+    let mut copied = 0;
+    for component in COMPONENT_REGEX.find_iter(rsx) {
+        if component.start() < copied || strings[component.start()] {
+            continue;
+        }
+        let open = component.end() - 1;
+        let close = block_end(rsx, open);
+        if close >= rsx.len() {
+            continue;
+        }
+        let body = fill_slots(&rsx[open + 1..close]);
+        let (slots, rest) = take_slots(&body);
+        output += &rsx[copied..=open];
+        for (name, elements) in slots {
+            output += &format!(" {name}: rsx! {{ {} }},", elements.join(" "));
+        }
+        output += &rest;
+        copied = close;
+    }
+    output += &rsx[copied..];
+    output
+}
+
+// Remove the top level elements with a slot attribute from the body of a component. Returns the elements grouped by slot and the rest of the body
+fn take_slots(body: &str) -> (Vec<(String, Vec<String>)>, String) {
+    let strings = string_mask(body);
+    let depths = brace_depths(body, &strings);
+
+    let mut slots: Vec<(String, Vec<String>)> = Vec::new();
+    let mut rest = String::new();
+    let mut copied = 0;
+    for element in ELEMENT_REGEX.find_iter(body) {
+        if element.start() < copied || strings[element.start()] || depths[element.start()] != 0 {
+            continue;
+        }
+        let open = element.end() - 1;
+        let close = block_end(body, open);
+        if close >= body.len() {
+            continue;
+        }
+        let inner = &body[open + 1..close];
+        let (attributes, _) = top_level_attributes(inner);
+        let Some((_, slot)) = attributes.iter().find(|(attribute, _)| attribute == "slot") else {
+            continue;
+        };
+        let slot = prop_name(slot);
+        let filled = format!(
+            "{}{}}}",
+            &body[element.start()..=open],
+            SLOT_REGEX.replace(inner, "")
+        );
+        match slots.iter_mut().find(|(name, _)| *name == slot) {
+            Some((_, elements)) => elements.push(filled),
+            None => slots.push((slot, vec![filled])),
+        }
+        rest += &body[copied..element.start()];
+        copied = close + 1;
+    }
+    rest += &body[copied..];
+    (slots, rest)
+}
+
+// The depth of braces at each byte, ignoring braces in strings
+fn brace_depths(rsx: &str, strings: &[bool]) -> Vec<usize> {
+    let mut depths = Vec::with_capacity(rsx.len());
+    let mut depth = 0usize;
+    for (index, byte) in rsx.bytes().enumerate() {
+        if !strings[index] && byte == b'}' {
+            depth = depth.saturating_sub(1);
+        }
+        depths.push(depth);
+        if !strings[index] && byte == b'{' {
+            depth += 1;
+        }
+    }
+    depths
+}
+
+#[test]
+fn named_slots() {
+    let rsx = r#"div {
+    Layout {
+        nav { slot: "header", class: "flex", "Home" }
+        p { "Body {name}" }
+        button { slot: "actions", "Save" }
+        button { slot: "actions", "Cancel" }
+    }
+}"#;
+    let filled = fill_slots(rsx);
+    assert!(filled.contains(
+        r#"Layout { header: rsx! { nav { class: "flex", "Home" } }, actions: rsx! { button { "Save" } button { "Cancel" } },"#
+    ));
+    assert!(filled.contains(r#"p { "Body {name}" }"#));
+    assert!(!filled.contains("slot:"));
+
+    let layout = r#"div {
+    header { "{header_slot}" }
+    main { "{children}" }
+    img { alt: "{image_slot}" }
+}"#;
+    let code = crate::rsx_to_component("Layout", "", layout, &[], crate::DioxusVersion::V0_5);
+    assert!(code.contains(
+        "pub fn Layout(#[props(default)] header: Element, children: Element, #[props(default)] image_slot: String)"
+    ));
+    assert!(code.contains("header { {header} }"));
+    assert!(code.contains(r#"alt: "{image_slot}""#));
+}
@@ -0,0 +1,1084 @@
+use crate::PartialState;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use Values::*;
+
+/// A class in the generated HTML that is not a Tailwind class
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassProblem {
+    /// The component the class is used in, or `None` for the app
+    pub component: Option<String>,
+    pub class: String,
+    /// The Tailwind class that replaces it, or `None` if there is no close class and the class is kept
+    pub fix: Option<String>,
+}
+
+impl Display for ClassProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let location = self.component.as_deref().unwrap_or("the app");
+        match &self.fix {
+            Some(fix) => write!(
+                f,
+                "unknown Tailwind class {:?} in {location}, replaced with {fix:?}",
+                self.class
+            ),
+            None => write!(
+                f,
+                "unknown Tailwind class {:?} in {location}, kept",
+                self.class
+            ),
+        }
+    }
+}
+
+impl PartialState {
+    /// Every Tailwind class used in the app and the components, without classes that contain placeholders
+    pub fn classes(&self) -> BTreeSet<String> {
+        std::iter::once(&self.html)
+            .chain(self.components.iter().map(|component| &component.html))
+            .flat_map(|html| html_classes(html))
+            .filter(|class| is_tailwind_class(class))
+            .collect()
+    }
+
+    /// Find the classes in the app and the components that are not Tailwind classes
+    pub fn check_classes(&self) -> Vec<ClassProblem> {
+        let mut problems = Vec::new();
+        let (_, app) = fix_html_classes(&self.html);
+        problems.extend(app.into_iter().map(|(class, fix)| ClassProblem {
+            component: None,
+            class,
+            fix,
+        }));
+        for component in &self.components {
+            let (_, fixes) = fix_html_classes(&component.html);
+            problems.extend(fixes.into_iter().map(|(class, fix)| ClassProblem {
+                component: Some(component.name.clone()),
+                class,
+                fix,
+            }));
+        }
+        problems
+    }
+
+    /// Replace every class that is not a Tailwind class with the closest Tailwind class. Classes without a close Tailwind class are kept, because they may come from other CSS. Returns every class that is not a Tailwind class.
+    pub fn fix_classes(&mut self) -> Vec<ClassProblem> {
+        let problems = self.check_classes();
+        self.html = fix_html_classes(&self.html).0;
+        for component in &mut self.components {
+            component.html = fix_html_classes(&component.html).0;
+        }
+        problems
+    }
+}
+
+// The static classes in every class attribute of the HTML
+fn html_classes(html: &str) -> Vec<String> {
+    let class_regex = Regex::new(r#"\bclass="([^"]*)""#).unwrap();
+    class_regex
+        .captures_iter(html)
+        .flat_map(|captures| {
+            captures[1]
+                .split_whitespace()
+                .filter(|class| !class.contains(['{', '}']))
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// Fix the classes in every class attribute of the HTML. Returns the new HTML and each invalid class with its fix. Classes without a fix are left in place
+fn fix_html_classes(html: &str) -> (String, Vec<(String, Option<String>)>) {
+    let class_regex = Regex::new(r#"\bclass="([^"]*)""#).unwrap();
+    let mut problems = Vec::new();
+    let html = class_regex.replace_all(html, |captures: &regex::Captures| {
+        let mut classes = Vec::new();
+        for class in captures[1].split_whitespace() {
+            // Placeholders are filled in at runtime
+            if class.contains(['{', '}']) || is_tailwind_class(class) {
+                classes.push(class.to_string());
+                continue;
+            }
+            let fix = fix_class(class);
+            let replacement = fix.clone().unwrap_or_else(|| class.to_string());
+            if !classes.contains(&replacement) {
+                classes.push(replacement);
+            }
+            problems.push((class.to_string(), fix));
+        }
+        format!("class=\"{}\"", classes.join(" "))
+    });
+    (html.to_string(), problems)
+}
+
+/// Returns true if the class is a Tailwind utility with any number of valid variants like `md:hover:bg-blue-500`
+pub(crate) fn is_tailwind_class(class: &str) -> bool {
+    let (variants, utility) = split_variants(class);
+    variants.iter().all(|variant| is_variant(variant)) && is_utility(utility)
+}
+
+// Split `md:hover:bg-blue-500` into the variants and the utility. Colons inside arbitrary values are not separators
+fn split_variants(class: &str) -> (Vec<&str>, &str) {
+    let mut variants = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in class.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ':' if depth == 0 => {
+                variants.push(&class[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    (variants, &class[start..])
+}
+
+fn is_variant(variant: &str) -> bool {
+    if is_arbitrary(variant) || VARIANTS.contains(&variant) {
+        return true;
+    }
+    // group-hover, peer-focus, min-[400px], data-[state=open], aria-checked
+    if let Some(state) = variant
+        .strip_prefix("group-")
+        .or_else(|| variant.strip_prefix("peer-"))
+    {
+        return VARIANTS.contains(&state) || is_arbitrary(state);
+    }
+    ["min-", "max-", "data-", "supports-"]
+        .iter()
+        .any(|prefix| variant.strip_prefix(prefix).is_some_and(is_arbitrary))
+        || variant
+            .strip_prefix("aria-")
+            .is_some_and(|aria| is_arbitrary(aria) || ARIA_STATES.contains(&aria))
+}
+
+fn is_arbitrary(value: &str) -> bool {
+    value.len() > 2 && value.starts_with('[') && value.ends_with(']')
+}
+
+fn is_utility(utility: &str) -> bool {
+    let utility = utility.strip_prefix('!').unwrap_or(utility);
+    if STATIC_CLASSES.contains(&utility) {
+        return true;
+    }
+    let (negative, utility) = match utility.strip_prefix('-') {
+        Some(utility) => (true, utility),
+        None => (false, utility),
+    };
+    RULES.iter().any(|rule| {
+        if negative && !rule.negative {
+            return false;
+        }
+        let value = if utility == rule.prefix {
+            ""
+        } else {
+            match utility
+                .strip_prefix(rule.prefix)
+                .and_then(|rest| rest.strip_prefix('-'))
+            {
+                Some(value) => value,
+                None => return false,
+            }
+        };
+        rule.values.iter().any(|values| values.contains(value))
+    })
+}
+
+// Common mistakes, mostly from other CSS frameworks, and the Tailwind class they mean
+const REPLACEMENTS: &[(&str, &str)] = &[
+    ("grey", "gray"),
+    ("d-flex", "flex"),
+    ("d-none", "hidden"),
+    ("d-block", "block"),
+    ("d-inline-block", "inline-block"),
+    ("d-grid", "grid"),
+    ("flex-column", "flex-col"),
+    ("flex-direction-column", "flex-col"),
+    ("flex-direction-row", "flex-row"),
+    ("justify-content", "justify"),
+    ("align-items", "items"),
+    ("align-content", "content"),
+    ("align-self", "self"),
+    ("align-center", "items-center"),
+    ("text-bold", "font-bold"),
+    ("text-semibold", "font-semibold"),
+    ("font-weight", "font"),
+    ("text-italic", "italic"),
+    ("text-underline", "underline"),
+    ("text-uppercase", "uppercase"),
+    ("text-lowercase", "lowercase"),
+    ("rounded-circle", "rounded-full"),
+    ("rounded-pill", "rounded-full"),
+    ("position-relative", "relative"),
+    ("position-absolute", "absolute"),
+    ("w-100", "w-full"),
+    ("h-100", "h-full"),
+    ("font-size", "text"),
+    ("border-radius", "rounded"),
+    ("line-height", "leading"),
+    ("letter-spacing", "tracking"),
+];
+
+// Find the closest Tailwind class to a class that is not a Tailwind class
+fn fix_class(class: &str) -> Option<String> {
+    let (variants, utility) = split_variants(class);
+    let variants = variants
+        .into_iter()
+        .filter(|variant| is_variant(variant))
+        .map(|variant| format!("{variant}:"))
+        .collect::<String>();
+    let with_variants =
+        |utility: String| is_utility(&utility).then(|| format!("{variants}{utility}"));
+
+    if is_utility(utility) {
+        return Some(format!("{variants}{utility}"));
+    }
+
+    // Replace each mistake that is a whole part of the class
+    let mut replaced = utility.to_string();
+    for (mistake, replacement) in REPLACEMENTS {
+        let starts_word = |index: usize| index == 0 || replaced.as_bytes()[index - 1] == b'-';
+        let ends_word =
+            |index: usize| index == replaced.len() || replaced.as_bytes()[index] == b'-';
+        if let Some(index) = replaced.find(mistake) {
+            if starts_word(index) && ends_word(index + mistake.len()) {
+                replaced.replace_range(index..index + mistake.len(), replacement);
+            }
+        }
+    }
+    if let Some(fixed) = with_variants(replaced.clone()) {
+        return Some(fixed);
+    }
+
+    // Round numbers that are not on the scale like `p-13` or `bg-blue-550` to the closest value
+    let (prefix, number) = replaced.rsplit_once('-')?;
+    let number = number.parse::<f64>().ok()?;
+    NUMBERS
+        .iter()
+        .filter_map(|candidate| {
+            let fixed = with_variants(format!("{prefix}-{candidate}"))?;
+            let distance = (candidate.parse::<f64>().ok()? - number).abs();
+            Some((distance, fixed))
+        })
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, fixed)| fixed)
+}
+
+// The values a utility accepts after its prefix. An empty string means the prefix is a class on its own
+enum Values {
+    Spacing,
+    Size,
+    Color,
+    Words(&'static [&'static str]),
+}
+
+impl Values {
+    fn contains(&self, value: &str) -> bool {
+        if is_arbitrary(value) {
+            return true;
+        }
+        match self {
+            Values::Spacing => SPACING.contains(&value),
+            Values::Size => SPACING.contains(&value) || SIZES.contains(&value),
+            Values::Color => {
+                // Colors take an opacity modifier like `bg-black/50`
+                let (color, opacity) = match value.split_once('/') {
+                    Some((color, opacity)) => (color, Some(opacity)),
+                    None => (value, None),
+                };
+                let opacity_valid = opacity
+                    .is_none_or(|opacity| is_arbitrary(opacity) || OPACITY.contains(&opacity));
+                let color_valid = SPECIAL_COLORS.contains(&color)
+                    || color.rsplit_once('-').is_some_and(|(name, shade)| {
+                        COLORS.contains(&name) && SHADES.contains(&shade)
+                    });
+                opacity_valid && color_valid
+            }
+            Values::Words(words) => words.contains(&value),
+        }
+    }
+}
+
+struct Rule {
+    prefix: &'static str,
+    values: &'static [Values],
+    // If the utility takes a leading minus like `-mt-4`
+    negative: bool,
+}
+
+const fn rule(prefix: &'static str, values: &'static [Values]) -> Rule {
+    Rule {
+        prefix,
+        values,
+        negative: false,
+    }
+}
+
+const fn negative(prefix: &'static str, values: &'static [Values]) -> Rule {
+    Rule {
+        prefix,
+        values,
+        negative: true,
+    }
+}
+
+const SPACING: &[&str] = &[
+    "0", "px", "0.5", "1", "1.5", "2", "2.5", "3", "3.5", "4", "5", "6", "7", "8", "9", "10", "11",
+    "12", "14", "16", "20", "24", "28", "32", "36", "40", "44", "48", "52", "56", "60", "64", "72",
+    "80", "96",
+];
+
+const SIZES: &[&str] = &[
+    "auto", "full", "screen", "svw", "svh", "lvw", "lvh", "dvw", "dvh", "min", "max", "fit", "1/2",
+    "1/3", "2/3", "1/4", "2/4", "3/4", "1/5", "2/5", "3/5", "4/5", "1/6", "2/6", "3/6", "4/6",
+    "5/6", "1/12", "2/12", "3/12", "4/12", "5/12", "6/12", "7/12", "8/12", "9/12", "10/12",
+    "11/12",
+];
+
+const COLORS: &[&str] = &[
+    "slate", "gray", "zinc", "neutral", "stone", "red", "orange", "amber", "yellow", "lime",
+    "green", "emerald", "teal", "cyan", "sky", "blue", "indigo", "violet", "purple", "fuchsia",
+    "pink", "rose",
+];
+
+const SHADES: &[&str] = &[
+    "50", "100", "200", "300", "400", "500", "600", "700", "800", "900", "950",
+];
+
+const SPECIAL_COLORS: &[&str] = &["inherit", "current", "transparent", "black", "white"];
+
+const OPACITY: &[&str] = &[
+    "0", "5", "10", "15", "20", "25", "30", "35", "40", "45", "50", "55", "60", "65", "70", "75",
+    "80", "85", "90", "95", "100",
+];
+
+// Every number on a Tailwind scale, used to round numbers that are not on the scale
+const NUMBERS: &[&str] = &[
+    "0", "0.5", "1", "1.5", "2", "2.5", "3", "3.5", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+    "13", "14", "15", "16", "20", "24", "25", "28", "30", "32", "35", "36", "40", "44", "45", "48",
+    "50", "52", "55", "56", "60", "64", "65", "70", "72", "75", "80", "85", "90", "95", "96",
+    "100", "105", "110", "125", "150", "180", "200", "300", "400", "500", "600", "700", "800",
+    "900", "950", "1000",
+];
+
+const WIDTHS: &[&str] = &["", "0", "2", "4", "8"];
+const SHADOWS: &[&str] = &["", "sm", "md", "lg", "xl", "2xl", "none"];
+const RADII: &[&str] = &["", "none", "sm", "md", "lg", "xl", "2xl", "3xl", "full"];
+const BLURS: &[&str] = &["", "none", "sm", "md", "lg", "xl", "2xl", "3xl"];
+const FILTER_SCALE: &[&str] = &[
+    "0", "50", "75", "90", "95", "100", "105", "110", "125", "150", "200",
+];
+const DURATIONS: &[&str] = &["0", "75", "100", "150", "200", "300", "500", "700", "1000"];
+const GRID_LINES: &[&str] = &[
+    "auto", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13",
+];
+const SPANS: &[&str] = &[
+    "auto", "full", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+];
+const TRACKS: &[&str] = &[
+    "none", "subgrid", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+];
+const OVERFLOW: &[&str] = &["auto", "hidden", "clip", "visible", "scroll"];
+const POSITIONS: &[&str] = &[
+    "bottom",
+    "center",
+    "left",
+    "left-bottom",
+    "left-top",
+    "right",
+    "right-bottom",
+    "right-top",
+    "top",
+];
+const SELF_ALIGN: &[&str] = &["auto", "start", "end", "center", "stretch", "baseline"];
+const CONTENT_ALIGN: &[&str] = &[
+    "normal", "start", "end", "center", "between", "around", "evenly", "stretch", "baseline",
+];
+const MAX_WIDTHS: &[&str] = &[
+    "none",
+    "0",
+    "xs",
+    "sm",
+    "md",
+    "lg",
+    "xl",
+    "2xl",
+    "3xl",
+    "4xl",
+    "5xl",
+    "6xl",
+    "7xl",
+    "full",
+    "min",
+    "max",
+    "fit",
+    "prose",
+    "screen-sm",
+    "screen-md",
+    "screen-lg",
+    "screen-xl",
+    "screen-2xl",
+];
+
+const RULES: &[Rule] = &[
+    rule("p", &[Spacing]),
+    rule("px", &[Spacing]),
+    rule("py", &[Spacing]),
+    rule("pt", &[Spacing]),
+    rule("pr", &[Spacing]),
+    rule("pb", &[Spacing]),
+    rule("pl", &[Spacing]),
+    rule("ps", &[Spacing]),
+    rule("pe", &[Spacing]),
+    negative("m", &[Spacing, Words(&["auto"])]),
+    negative("mx", &[Spacing, Words(&["auto"])]),
+    negative("my", &[Spacing, Words(&["auto"])]),
+    negative("mt", &[Spacing, Words(&["auto"])]),
+    negative("mr", &[Spacing, Words(&["auto"])]),
+    negative("mb", &[Spacing, Words(&["auto"])]),
+    negative("ml", &[Spacing, Words(&["auto"])]),
+    negative("ms", &[Spacing, Words(&["auto"])]),
+    negative("me", &[Spacing, Words(&["auto"])]),
+    negative("space-x", &[Spacing, Words(&["reverse"])]),
+    negative("space-y", &[Spacing, Words(&["reverse"])]),
+    rule("gap", &[Spacing]),
+    rule("gap-x", &[Spacing]),
+    rule("gap-y", &[Spacing]),
+    rule("w", &[Size]),
+    rule("h", &[Size]),
+    rule("size", &[Size]),
+    rule("min-w", &[Size]),
+    rule("min-h", &[Size]),
+    rule("max-w", &[Spacing, Words(MAX_WIDTHS)]),
+    rule("max-h", &[Size, Words(&["none"])]),
+    rule("basis", &[Size]),
+    negative("inset", &[Size]),
+    negative("inset-x", &[Size]),
+    negative("inset-y", &[Size]),
+    negative("top", &[Size]),
+    negative("right", &[Size]),
+    negative("bottom", &[Size]),
+    negative("left", &[Size]),
+    negative("start", &[Size]),
+    negative("end", &[Size]),
+    negative("translate-x", &[Size]),
+    negative("translate-y", &[Size]),
+    rule(
+        "text",
+        &[
+            Color,
+            Words(&[
+                "xs", "sm", "base", "lg", "xl", "2xl", "3xl", "4xl", "5xl", "6xl", "7xl", "8xl",
+                "9xl", "left", "center", "right", "justify", "start", "end", "wrap", "nowrap",
+                "balance", "pretty", "ellipsis", "clip",
+            ]),
+        ],
+    ),
+    rule(
+        "font",
+        &[Words(&[
+            "thin",
+            "extralight",
+            "light",
+            "normal",
+            "medium",
+            "semibold",
+            "bold",
+            "extrabold",
+            "black",
+            "sans",
+            "serif",
+            "mono",
+        ])],
+    ),
+    rule(
+        "leading",
+        &[Words(&[
+            "none", "tight", "snug", "normal", "relaxed", "loose", "3", "4", "5", "6", "7", "8",
+            "9", "10",
+        ])],
+    ),
+    negative(
+        "tracking",
+        &[Words(&[
+            "tighter", "tight", "normal", "wide", "wider", "widest",
+        ])],
+    ),
+    rule(
+        "line-clamp",
+        &[Words(&["none", "1", "2", "3", "4", "5", "6"])],
+    ),
+    rule(
+        "bg",
+        &[
+            Color,
+            Words(&[
+                "none",
+                "fixed",
+                "local",
+                "scroll",
+                "repeat",
+                "no-repeat",
+                "repeat-x",
+                "repeat-y",
+                "auto",
+                "cover",
+                "contain",
+                "gradient-to-t",
+                "gradient-to-tr",
+                "gradient-to-r",
+                "gradient-to-br",
+                "gradient-to-b",
+                "gradient-to-bl",
+                "gradient-to-l",
+                "gradient-to-tl",
+                "clip-border",
+                "clip-padding",
+                "clip-content",
+                "clip-text",
+            ]),
+            Words(POSITIONS),
+        ],
+    ),
+    rule("from", &[Color]),
+    rule("via", &[Color]),
+    rule("to", &[Color]),
+    rule(
+        "border",
+        &[
+            Color,
+            Words(WIDTHS),
+            Words(&[
+                "solid", "dashed", "dotted", "double", "hidden", "none", "collapse", "separate",
+            ]),
+        ],
+    ),
+    rule("border-spacing", &[Spacing]),
+    rule("border-spacing-x", &[Spacing]),
+    rule("border-spacing-y", &[Spacing]),
+    rule("border-x", &[Color, Words(WIDTHS)]),
+    rule("border-y", &[Color, Words(WIDTHS)]),
+    rule("border-t", &[Color, Words(WIDTHS)]),
+    rule("border-r", &[Color, Words(WIDTHS)]),
+    rule("border-b", &[Color, Words(WIDTHS)]),
+    rule("border-l", &[Color, Words(WIDTHS)]),
+    rule("border-s", &[Color, Words(WIDTHS)]),
+    rule("border-e", &[Color, Words(WIDTHS)]),
+    rule(
+        "divide",
+        &[
+            Color,
+            Words(&["solid", "dashed", "dotted", "double", "none"]),
+        ],
+    ),
+    rule("divide-x", &[Words(WIDTHS), Words(&["reverse"])]),
+    rule("divide-y", &[Words(WIDTHS), Words(&["reverse"])]),
+    rule(
+        "outline",
+        &[
+            Color,
+            Words(&[
+                "", "none", "dashed", "dotted", "double", "0", "1", "2", "4", "8",
+            ]),
+        ],
+    ),
+    rule("outline-offset", &[Words(&["0", "1", "2", "4", "8"])]),
+    rule(
+        "ring",
+        &[Color, Words(&["", "0", "1", "2", "4", "8", "inset"])],
+    ),
+    rule("ring-offset", &[Color, Words(&["0", "1", "2", "4", "8"])]),
+    rule("rounded", &[Words(RADII)]),
+    rule("rounded-t", &[Words(RADII)]),
+    rule("rounded-r", &[Words(RADII)]),
+    rule("rounded-b", &[Words(RADII)]),
+    rule("rounded-l", &[Words(RADII)]),
+    rule("rounded-s", &[Words(RADII)]),
+    rule("rounded-e", &[Words(RADII)]),
+    rule("rounded-tl", &[Words(RADII)]),
+    rule("rounded-tr", &[Words(RADII)]),
+    rule("rounded-br", &[Words(RADII)]),
+    rule("rounded-bl", &[Words(RADII)]),
+    rule("shadow", &[Color, Words(SHADOWS), Words(&["inner"])]),
+    rule("opacity", &[Words(OPACITY)]),
+    // The opacity utilities of Tailwind 2, still supported by Tailwind 3
+    rule("bg-opacity", &[Words(OPACITY)]),
+    rule("text-opacity", &[Words(OPACITY)]),
+    rule("border-opacity", &[Words(OPACITY)]),
+    rule("divide-opacity", &[Words(OPACITY)]),
+    rule("ring-opacity", &[Words(OPACITY)]),
+    rule("placeholder-opacity", &[Words(OPACITY)]),
+    negative("z", &[Words(&["0", "10", "20", "30", "40", "50", "auto"])]),
+    negative(
+        "order",
+        &[Words(&[
+            "first", "last", "none", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+        ])],
+    ),
+    rule(
+        "flex",
+        &[Words(&[
+            "1",
+            "auto",
+            "initial",
+            "none",
+            "row",
+            "row-reverse",
+            "col",
+            "col-reverse",
+            "wrap",
+            "wrap-reverse",
+            "nowrap",
+        ])],
+    ),
+    rule("grow", &[Words(&["", "0"])]),
+    rule("shrink", &[Words(&["", "0"])]),
+    rule("flex-grow", &[Words(&["", "0"])]),
+    rule("flex-shrink", &[Words(&["", "0"])]),
+    rule("grid-cols", &[Words(TRACKS)]),
+    rule("grid-rows", &[Words(TRACKS)]),
+    rule("auto-cols", &[Words(&["auto", "min", "max", "fr"])]),
+    rule("auto-rows", &[Words(&["auto", "min", "max", "fr"])]),
+    rule("col", &[Words(&["auto"])]),
+    rule("row", &[Words(&["auto"])]),
+    rule(
+        "grid-flow",
+        &[Words(&["row", "col", "dense", "row-dense", "col-dense"])],
+    ),
+    rule("col-span", &[Words(SPANS)]),
+    rule("col-start", &[Words(GRID_LINES)]),
+    rule("col-end", &[Words(GRID_LINES)]),
+    rule("row-span", &[Words(SPANS)]),
+    rule("row-start", &[Words(GRID_LINES)]),
+    rule("row-end", &[Words(GRID_LINES)]),
+    rule("justify", &[Words(CONTENT_ALIGN)]),
+    rule(
+        "justify-items",
+        &[Words(&["start", "end", "center", "stretch"])],
+    ),
+    rule("justify-self", &[Words(SELF_ALIGN)]),
+    rule(
+        "items",
+        &[Words(&["start", "end", "center", "baseline", "stretch"])],
+    ),
+    rule("content", &[Words(CONTENT_ALIGN), Words(&["none"])]),
+    rule("self", &[Words(SELF_ALIGN)]),
+    rule("place-content", &[Words(CONTENT_ALIGN)]),
+    rule("place-items", &[Words(SELF_ALIGN)]),
+    rule("place-self", &[Words(SELF_ALIGN)]),
+    rule(
+        "align",
+        &[Words(&[
+            "baseline",
+            "top",
+            "middle",
+            "bottom",
+            "text-top",
+            "text-bottom",
+            "sub",
+            "super",
+        ])],
+    ),
+    rule(
+        "object",
+        &[
+            Words(&["contain", "cover", "fill", "none", "scale-down"]),
+            Words(POSITIONS),
+        ],
+    ),
+    rule("overflow", &[Words(OVERFLOW)]),
+    rule("overflow-x", &[Words(OVERFLOW)]),
+    rule("overflow-y", &[Words(OVERFLOW)]),
+    rule(
+        "cursor",
+        &[Words(&[
+            "auto",
+            "default",
+            "pointer",
+            "wait",
+            "text",
+            "move",
+            "help",
+            "not-allowed",
+            "none",
+            "progress",
+            "crosshair",
+            "grab",
+            "grabbing",
+            "zoom-in",
+            "zoom-out",
+        ])],
+    ),
+    rule(
+        "transition",
+        &[Words(&[
+            "",
+            "none",
+            "all",
+            "colors",
+            "opacity",
+            "shadow",
+            "transform",
+        ])],
+    ),
+    rule("duration", &[Words(DURATIONS)]),
+    rule("delay", &[Words(DURATIONS)]),
+    rule("ease", &[Words(&["linear", "in", "out", "in-out"])]),
+    rule(
+        "animate",
+        &[Words(&["none", "spin", "ping", "pulse", "bounce"])],
+    ),
+    negative(
+        "scale",
+        &[Words(&[
+            "0", "50", "75", "90", "95", "100", "105", "110", "125", "150",
+        ])],
+    ),
+    negative(
+        "scale-x",
+        &[Words(&[
+            "0", "50", "75", "90", "95", "100", "105", "110", "125", "150",
+        ])],
+    ),
+    negative(
+        "scale-y",
+        &[Words(&[
+            "0", "50", "75", "90", "95", "100", "105", "110", "125", "150",
+        ])],
+    ),
+    negative(
+        "rotate",
+        &[Words(&["0", "1", "2", "3", "6", "12", "45", "90", "180"])],
+    ),
+    negative("skew-x", &[Words(&["0", "1", "2", "3", "6", "12"])]),
+    negative("skew-y", &[Words(&["0", "1", "2", "3", "6", "12"])]),
+    rule(
+        "origin",
+        &[Words(&[
+            "center",
+            "top",
+            "top-right",
+            "right",
+            "bottom-right",
+            "bottom",
+            "bottom-left",
+            "left",
+            "top-left",
+        ])],
+    ),
+    rule("blur", &[Words(BLURS)]),
+    rule("backdrop-blur", &[Words(BLURS)]),
+    rule("drop-shadow", &[Words(SHADOWS)]),
+    rule("brightness", &[Words(FILTER_SCALE)]),
+    rule("contrast", &[Words(FILTER_SCALE)]),
+    rule("saturate", &[Words(&["0", "50", "100", "150", "200"])]),
+    negative(
+        "hue-rotate",
+        &[Words(&["0", "15", "30", "60", "90", "180"])],
+    ),
+    rule("grayscale", &[Words(&["", "0"])]),
+    rule("invert", &[Words(&["", "0"])]),
+    rule("sepia", &[Words(&["", "0"])]),
+    rule("backdrop-brightness", &[Words(FILTER_SCALE)]),
+    rule("backdrop-contrast", &[Words(FILTER_SCALE)]),
+    rule("backdrop-grayscale", &[Words(&["", "0"])]),
+    rule("backdrop-opacity", &[Words(OPACITY)]),
+    rule(
+        "backdrop-saturate",
+        &[Words(&["0", "50", "100", "150", "200"])],
+    ),
+    rule(
+        "mix-blend",
+        &[Words(&[
+            "normal",
+            "multiply",
+            "screen",
+            "overlay",
+            "darken",
+            "lighten",
+            "color-dodge",
+            "color-burn",
+            "hard-light",
+            "soft-light",
+            "difference",
+            "exclusion",
+            "hue",
+            "saturation",
+            "color",
+            "luminosity",
+            "plus-lighter",
+        ])],
+    ),
+    rule("aspect", &[Words(&["auto", "square", "video"])]),
+    rule(
+        "columns",
+        &[Words(&[
+            "auto", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12",
+        ])],
+    ),
+    rule(
+        "list",
+        &[Words(&["none", "disc", "decimal", "inside", "outside"])],
+    ),
+    rule(
+        "decoration",
+        &[
+            Color,
+            Words(&[
+                "solid",
+                "double",
+                "dotted",
+                "dashed",
+                "wavy",
+                "auto",
+                "from-font",
+                "0",
+                "1",
+                "2",
+                "4",
+                "8",
+            ]),
+        ],
+    ),
+    rule(
+        "underline-offset",
+        &[Words(&["auto", "0", "1", "2", "4", "8"])],
+    ),
+    rule("fill", &[Color, Words(&["none"])]),
+    rule("stroke", &[Color, Words(&["none", "0", "1", "2"])]),
+    rule("accent", &[Color, Words(&["auto"])]),
+    rule("caret", &[Color]),
+    rule("placeholder", &[Color]),
+    rule("select", &[Words(&["none", "text", "all", "auto"])]),
+    rule("pointer-events", &[Words(&["none", "auto"])]),
+    rule("resize", &[Words(&["", "none", "x", "y"])]),
+    rule(
+        "whitespace",
+        &[Words(&[
+            "normal",
+            "nowrap",
+            "pre",
+            "pre-line",
+            "pre-wrap",
+            "break-spaces",
+        ])],
+    ),
+    rule("break", &[Words(&["normal", "words", "all", "keep"])]),
+    rule(
+        "break-inside",
+        &[Words(&["auto", "avoid", "avoid-page", "avoid-column"])],
+    ),
+    negative("indent", &[Spacing]),
+    rule("hyphens", &[Words(&["none", "manual", "auto"])]),
+    rule("overscroll", &[Words(&["auto", "contain", "none"])]),
+    rule("overscroll-x", &[Words(&["auto", "contain", "none"])]),
+    rule("overscroll-y", &[Words(&["auto", "contain", "none"])]),
+    rule(
+        "touch",
+        &[Words(&["auto", "none", "pan-x", "pan-y", "manipulation"])],
+    ),
+    rule(
+        "will-change",
+        &[Words(&["auto", "scroll", "contents", "transform"])],
+    ),
+    rule(
+        "float",
+        &[Words(&["start", "end", "right", "left", "none"])],
+    ),
+    rule(
+        "clear",
+        &[Words(&["start", "end", "left", "right", "both", "none"])],
+    ),
+    rule("box", &[Words(&["border", "content"])]),
+    rule("table", &[Words(&["auto", "fixed"])]),
+    rule("scroll", &[Words(&["auto", "smooth"])]),
+    rule(
+        "snap",
+        &[Words(&[
+            "start",
+            "end",
+            "center",
+            "none",
+            "x",
+            "y",
+            "both",
+            "mandatory",
+            "proximity",
+        ])],
+    ),
+];
+
+const STATIC_CLASSES: &[&str] = &[
+    "block",
+    "inline-block",
+    "inline",
+    "flex",
+    "inline-flex",
+    "grid",
+    "inline-grid",
+    "table",
+    "table-row",
+    "table-cell",
+    "table-caption",
+    "table-header-group",
+    "table-row-group",
+    "flow-root",
+    "contents",
+    "list-item",
+    "hidden",
+    "static",
+    "fixed",
+    "absolute",
+    "relative",
+    "sticky",
+    "visible",
+    "invisible",
+    "collapse",
+    "isolate",
+    "isolation-auto",
+    "container",
+    "sr-only",
+    "not-sr-only",
+    "truncate",
+    "italic",
+    "not-italic",
+    "underline",
+    "overline",
+    "line-through",
+    "no-underline",
+    "uppercase",
+    "lowercase",
+    "capitalize",
+    "normal-case",
+    "antialiased",
+    "subpixel-antialiased",
+    "tabular-nums",
+    "ordinal",
+    "transform",
+    "transform-gpu",
+    "transform-none",
+    "filter",
+    "filter-none",
+    "backdrop-filter",
+    "appearance-none",
+    "outline-none",
+    "prose",
+    "group",
+    "peer",
+];
+
+const VARIANTS: &[&str] = &[
+    "sm",
+    "md",
+    "lg",
+    "xl",
+    "2xl",
+    "max-sm",
+    "max-md",
+    "max-lg",
+    "max-xl",
+    "max-2xl",
+    "dark",
+    "motion-safe",
+    "motion-reduce",
+    "print",
+    "portrait",
+    "landscape",
+    "rtl",
+    "ltr",
+    "hover",
+    "focus",
+    "focus-within",
+    "focus-visible",
+    "active",
+    "visited",
+    "target",
+    "first",
+    "last",
+    "only",
+    "odd",
+    "even",
+    "first-of-type",
+    "last-of-type",
+    "empty",
+    "disabled",
+    "enabled",
+    "checked",
+    "indeterminate",
+    "default",
+    "required",
+    "valid",
+    "invalid",
+    "in-range",
+    "out-of-range",
+    "placeholder-shown",
+    "autofill",
+    "read-only",
+    "open",
+    "before",
+    "after",
+    "placeholder",
+    "file",
+    "marker",
+    "selection",
+    "first-line",
+    "first-letter",
+    "backdrop",
+    "*",
+];
+
+const ARIA_STATES: &[&str] = &[
+    "busy", "checked", "disabled", "expanded", "hidden", "pressed", "readonly", "required",
+    "selected",
+];
+
+#[test]
+fn tailwind_classes() {
+    for class in [
+        "flex",
+        "md:hover:bg-blue-500",
+        "bg-black/50",
+        "-mt-4",
+        "w-1/2",
+        "p-[13px]",
+        "group-hover:text-white",
+        "rounded",
+        "border-gray-200",
+        "!font-bold",
+        "flex-shrink-0",
+        "flex-grow",
+        "flex-grow-0",
+        "bg-opacity-50",
+        "text-opacity-75",
+        "drop-shadow-md",
+        "border-collapse",
+    ] {
+        assert!(is_tailwind_class(class), "{class} should be valid");
+    }
+    for class in ["btn", "bg-blue-550", "-p-4", "flex-column", "hovr:flex"] {
+        assert!(!is_tailwind_class(class), "{class} should be invalid");
+    }
+
+    assert_eq!(
+        fix_class("text-grey-500"),
+        Some("text-gray-500".to_string())
+    );
+    assert_eq!(fix_class("md:flex-column"), Some("md:flex-col".to_string()));
+    assert_eq!(fix_class("p-13"), Some("p-12".to_string()));
+    assert_eq!(fix_class("bg-blue-550"), Some("bg-blue-500".to_string()));
+    assert_eq!(fix_class("btn-primary"), None);
+
+    let (html, problems) = fix_html_classes(
+        r#"<div class="d-flex justify-content-between {extra}"><a class="btn">Go</a></div>"#,
+    );
+    assert_eq!(
+        html,
+        r#"<div class="flex justify-between {extra}"><a class="btn">Go</a></div>"#
+    );
+    assert_eq!(problems.len(), 3);
+    assert_eq!(problems[2], ("btn".to_string(), None));
+}
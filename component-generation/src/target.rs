@@ -0,0 +1,165 @@
+use crate::loops::DataList;
+use crate::naming::rust_ident;
+use crate::{Prop, PropType};
+
+/// The version of dioxus generated code is written for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DioxusVersion {
+    /// Dioxus 0.4. Components take a `Scope`, state is stored with `use_state` and the rsx is rendered with `cx.render`
+    V0_4,
+    /// Dioxus 0.5 and later. Components return an `Element` directly and state is stored in signals
+    #[default]
+    V0_5,
+}
+
+impl DioxusVersion {
+    /// The version requirement for the `dioxus` dependency
+    pub fn dependency(self) -> &'static str {
+        match self {
+            DioxusVersion::V0_4 => "0.4",
+            DioxusVersion::V0_5 => "0.5",
+        }
+    }
+
+    /// The signature of a component function with the props and the data for its loops
+    pub(crate) fn signature(self, name: &str, props: &[Prop], lists: &[DataList]) -> String {
+        match self {
+            DioxusVersion::V0_4 => {
+                // Elements and event handlers borrow from the scope
+                let borrows = props.iter().any(|prop| {
+                    matches!(
+                        prop.ty,
+                        PropType::Element
+                            | PropType::MouseEventHandler
+                            | PropType::FormEventHandler
+                    )
+                });
+                let (generics, scope) = match borrows {
+                    true => ("<'a>", "cx: Scope<'a>"),
+                    false => ("", "cx: Scope"),
+                };
+                let props = props.iter().map(|prop| {
+                    let ty = match prop.ty {
+                        PropType::Element => "Element<'a>".to_string(),
+                        PropType::MouseEventHandler => "EventHandler<'a, MouseEvent>".to_string(),
+                        PropType::FormEventHandler => "EventHandler<'a, FormEvent>".to_string(),
+                        _ => prop.ty.to_string(),
+                    };
+                    let default = if prop.optional {
+                        "#[props(default)] "
+                    } else {
+                        ""
+                    };
+                    format!("{default}{}: {ty}", rust_ident(&prop.name))
+                });
+                let params = std::iter::once(scope.to_string())
+                    .chain(props)
+                    .chain(lists.iter().map(DataList::param))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("pub fn {name}{generics}({params}) -> Element")
+            }
+            DioxusVersion::V0_5 => {
+                let params = props
+                    .iter()
+                    .map(ToString::to_string)
+                    .chain(lists.iter().map(DataList::param))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("pub fn {name}({params}) -> Element")
+            }
+        }
+    }
+
+    /// Wrap the rsx so it is the return value of a component
+    pub(crate) fn render(self, rsx: &str) -> String {
+        match self {
+            DioxusVersion::V0_4 => format!("cx.render(rsx! {{{rsx}\n    }})"),
+            DioxusVersion::V0_5 => format!("rsx! {{{rsx}\n    }}"),
+        }
+    }
+
+    /// An element written inline, like the value of a prop
+    pub(crate) fn inline_rsx(self, rsx: &str) -> String {
+        match self {
+            DioxusVersion::V0_4 => format!("cx.render(rsx! {{ {rsx} }})"),
+            DioxusVersion::V0_5 => format!("rsx! {{ {rsx} }}"),
+        }
+    }
+
+    /// The hook that stores a piece of state
+    pub(crate) fn state_hook(self, name: &str, initial: &str, mutable: bool) -> String {
+        match self {
+            DioxusVersion::V0_4 => format!("let {name} = use_state(cx, {initial});"),
+            DioxusVersion::V0_5 => {
+                let mutability = if mutable { "mut " } else { "" };
+                format!("let {mutability}{name} = use_signal({initial});")
+            }
+        }
+    }
+
+    /// Read the value of a piece of state
+    pub(crate) fn read(self, name: &str) -> String {
+        match self {
+            DioxusVersion::V0_4 => format!("*{name}.get()"),
+            DioxusVersion::V0_5 => format!("{name}()"),
+        }
+    }
+
+    /// Flip a bool piece of state
+    pub(crate) fn toggle(self, name: &str) -> String {
+        match self {
+            DioxusVersion::V0_4 => format!("{name}.modify(|value| !value)"),
+            DioxusVersion::V0_5 => format!("{name}.toggle()"),
+        }
+    }
+
+    /// The value of an `event` from an input
+    pub(crate) fn event_value(self) -> &'static str {
+        match self {
+            DioxusVersion::V0_4 => "event.value.clone()",
+            DioxusVersion::V0_5 => "event.value()",
+        }
+    }
+
+    /// The statement in `main` that launches the `app` component
+    pub(crate) fn launch(self) -> &'static str {
+        match self {
+            DioxusVersion::V0_4 => "dioxus_web::launch(app);",
+            DioxusVersion::V0_5 => "launch(app);",
+        }
+    }
+}
+
+#[test]
+fn component_targets() {
+    use crate::validate::validate_code;
+
+    let rsx = r#"
+div {
+    input { r#type: "checkbox", name: "subscribed" }
+    button { "Save" }
+    "{title}"
+    {children}
+}"#;
+    let code = crate::rsx_to_component("Settings", "", rsx, &[], DioxusVersion::V0_5);
+    assert!(code.contains("pub fn Settings(title: String, children: Element, #[props(default)] on_save: EventHandler<MouseEvent>) -> Element {"));
+    assert!(code.contains("let mut subscribed = use_signal(|| false);"));
+    assert!(code.contains(
+        "checked: subscribed(), oninput: move |event| subscribed.set(event.value() == \"true\")"
+    ));
+    assert!(code.contains("    rsx! {"));
+    assert_eq!(validate_code(&code), []);
+
+    let code = crate::rsx_to_component("Settings", "", rsx, &[], DioxusVersion::V0_4);
+    assert!(code.contains("pub fn Settings<'a>(cx: Scope<'a>, title: String, children: Element<'a>, #[props(default)] on_save: EventHandler<'a, MouseEvent>) -> Element {"));
+    assert!(code.contains("let subscribed = use_state(cx, || false);"));
+    assert!(code.contains("checked: *subscribed.get(), oninput: move |event| subscribed.set(event.value.clone() == \"true\")"));
+    assert!(code.contains("    cx.render(rsx! {"));
+    assert!(code.ends_with("    })\n}"));
+    assert_eq!(validate_code(&code), []);
+
+    let code =
+        crate::rsx_to_component("Title", "", r#"h1 { "{title}" }"#, &[], DioxusVersion::V0_4);
+    assert!(code.contains("pub fn Title(cx: Scope, title: String) -> Element {"));
+}
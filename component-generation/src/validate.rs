@@ -0,0 +1,596 @@
+use crate::icons::resolve_icons;
+use crate::{component_props, Component, GenerationError, PartialState, Prop, PropType};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use std::fmt::Display;
+use syn::{Expr, Item, Stmt};
+
+// The elements in `dioxus_elements`, including the svg elements icons are drawn with
+const HTML_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "big",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "param",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+    "svg",
+    "path",
+    "circle",
+    "ellipse",
+    "line",
+    "polyline",
+    "polygon",
+    "rect",
+    "g",
+    "defs",
+    "text",
+    "tspan",
+    "symbol",
+    "use",
+    "mask",
+    "pattern",
+    "image",
+    "clipPath",
+    "linearGradient",
+    "radialGradient",
+    "stop",
+    "filter",
+];
+
+// Components from dioxus and dioxus-router that generated code renders
+const DIOXUS_COMPONENTS: &[&str] = &["Link", "Outlet", "Router"];
+
+/// A problem found while checking generated code
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    /// The line of the problem in the generated code, starting at 1. The line is 0 if the problem is not in the code, like invalid HTML.
+    pub line: usize,
+    /// The column of the problem in the generated code, starting at 0
+    pub column: usize,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = if self.is_error() { "error" } else { "warning" };
+        write!(
+            f,
+            "{} {severity} at {}:{}: {}",
+            self.kind, self.line, self.column, self.message
+        )
+    }
+}
+
+/// What stage of the code generation a problem was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// The HTML the model generated could not be converted to rsx
+    Html,
+    /// The generated code is not valid rust
+    Rust,
+    /// The body of the `rsx!` macro is not valid rsx
+    Rsx,
+    /// An icon placeholder in the HTML does not match any known icon. The icon is rendered as an empty svg, so this is only a warning.
+    Icon,
+    /// An element is not an HTML element or a declared component, or a component is called without a required prop or with a prop it does not have
+    Element,
+}
+
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticKind::Html => write!(f, "HTML"),
+            DiagnosticKind::Rust => write!(f, "rust"),
+            DiagnosticKind::Rsx => write!(f, "rsx"),
+            DiagnosticKind::Icon => write!(f, "icon"),
+            DiagnosticKind::Element => write!(f, "element"),
+        }
+    }
+}
+
+impl Diagnostic {
+    /// Returns true if the problem stops the code from compiling. Warnings like unknown icons don't make a component invalid.
+    pub fn is_error(&self) -> bool {
+        self.kind != DiagnosticKind::Icon
+    }
+
+    fn from_syn(kind: DiagnosticKind, error: &syn::Error) -> Self {
+        let start = error.span().start();
+        Self {
+            kind,
+            message: error.to_string(),
+            line: start.line,
+            column: start.column,
+        }
+    }
+
+    fn element(token: &TokenTree, message: String) -> Self {
+        let start = token.span().start();
+        Self {
+            kind: DiagnosticKind::Element,
+            message,
+            line: start.line,
+            column: start.column,
+        }
+    }
+}
+
+impl From<GenerationError> for Diagnostic {
+    fn from(error: GenerationError) -> Self {
+        Self {
+            kind: DiagnosticKind::Html,
+            message: error.to_string(),
+            line: 0,
+            column: 0,
+        }
+    }
+}
+
+impl Component {
+    /// Check that the component converts to valid rust and valid rsx, that every element it renders exists and that every icon it uses exists. Calls to other components are only checked by [`Component::validate_with`].
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = match self.component_string() {
+            Ok(code) => validate_code(&code),
+            Err(err) => vec![err.into()],
+        };
+        diagnostics.extend(validate_icons(&self.html));
+        diagnostics
+    }
+
+    /// Like [`Component::validate`], but also check that the component only renders these components and passes each one its required props
+    pub fn validate_with(&self, components: &[Component]) -> Vec<Diagnostic> {
+        let mut diagnostics = match self.component_string_with(components) {
+            Ok(code) => {
+                let others = components
+                    .iter()
+                    .filter(|component| component.name != self.name)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                validate_calls(&code, &component_props(&others))
+            }
+            Err(err) => vec![err.into()],
+        };
+        diagnostics.extend(validate_icons(&self.html));
+        diagnostics
+    }
+
+    /// The problems found in the component the last time it was validated
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Returns true if the component had no errors the last time it was validated. It may still have warnings.
+    pub fn is_valid(&self) -> bool {
+        !self.diagnostics.iter().any(Diagnostic::is_error)
+    }
+}
+
+impl PartialState {
+    /// Validate the app and every component. The results are available from [`PartialState::app_diagnostics`] and [`Component::diagnostics`].
+    pub fn validate(&mut self) {
+        self.app_diagnostics = match self.app_component() {
+            Ok(code) => validate_calls(&code, &component_props(&self.components)),
+            Err(err) => vec![err.into()],
+        };
+        self.app_diagnostics.extend(validate_icons(&self.html));
+        let components = self.components.clone();
+        for component in &mut self.components {
+            component.diagnostics = component.validate_with(&components);
+        }
+    }
+
+    /// The problems found in the app the last time it was validated
+    pub fn app_diagnostics(&self) -> &[Diagnostic] {
+        &self.app_diagnostics
+    }
+
+    /// Returns true if the app and every component had no errors the last time they were validated
+    pub fn is_valid(&self) -> bool {
+        !self.app_diagnostics.iter().any(Diagnostic::is_error)
+            && self.components.iter().all(Component::is_valid)
+    }
+}
+
+// Icon placeholders that do not match any icon in the icon set
+fn validate_icons(html: &str) -> Vec<Diagnostic> {
+    let (_, unknown) = resolve_icons(html);
+    unknown
+        .into_iter()
+        .map(|name| Diagnostic {
+            kind: DiagnosticKind::Icon,
+            message: format!("unknown icon {name:?}"),
+            line: 0,
+            column: 0,
+        })
+        .collect()
+}
+
+/// Parse generated code as a rust file and parse the body of every `rsx!` call in it. Every lowercase element must be an HTML element. Calls to components are not checked.
+pub(crate) fn validate_code(code: &str) -> Vec<Diagnostic> {
+    check_code(code, None)
+}
+
+/// Like [`validate_code`], but every component the code calls must be one of the components and get all of its required props
+pub(crate) fn validate_calls(code: &str, components: &[(String, Vec<Prop>)]) -> Vec<Diagnostic> {
+    check_code(code, Some(components))
+}
+
+fn check_code(code: &str, components: Option<&[(String, Vec<Prop>)]>) -> Vec<Diagnostic> {
+    let file = match syn::parse_file(code) {
+        Ok(file) => file,
+        Err(err) => return vec![Diagnostic::from_syn(DiagnosticKind::Rust, &err)],
+    };
+
+    let mut diagnostics = Vec::new();
+    for item in &file.items {
+        let Item::Fn(function) = item else {
+            continue;
+        };
+        for mac in function.block.stmts.iter().filter_map(rsx_macro) {
+            match syn::parse2::<dioxus_rsx::CallBody>(mac.tokens.clone()) {
+                Ok(_) => check_body(mac.tokens.clone(), components, &mut diagnostics),
+                Err(err) => diagnostics.push(Diagnostic::from_syn(DiagnosticKind::Rsx, &err)),
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// The `rsx!` call of a statement in the body of a component
+pub(crate) fn rsx_macro(stmt: &Stmt) -> Option<&syn::Macro> {
+    let mac = match stmt {
+        Stmt::Macro(mac) => &mac.mac,
+        Stmt::Expr(Expr::Macro(mac), _) => &mac.mac,
+        // Dioxus 0.4 components return `cx.render(rsx! { ... })`
+        Stmt::Expr(Expr::MethodCall(call), _) if call.method == "render" => {
+            match call.args.first() {
+                Some(Expr::Macro(mac)) => &mac.mac,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    mac.path.is_ident("rsx").then_some(mac)
+}
+
+// Check the elements in the body of an `rsx!` call or an element, and the props passed to components
+fn check_body(
+    body: TokenStream,
+    components: Option<&[(String, Vec<Prop>)]>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let tokens = body.into_iter().collect::<Vec<_>>();
+    let mut index = 0;
+    while index < tokens.len() {
+        let TokenTree::Ident(ident) = &tokens[index] else {
+            index += 1;
+            continue;
+        };
+        let name = ident.to_string();
+        // Loops and conditions contain rsx in their braces
+        if matches!(name.as_str(), "for" | "if") {
+            match next_brace(&tokens, index) {
+                Some(body) => {
+                    if let TokenTree::Group(group) = &tokens[body] {
+                        check_body(group.stream(), components, diagnostics);
+                    }
+                    index = body + 1;
+                }
+                None => index = tokens.len(),
+            }
+            continue;
+        }
+        if is_attribute(&tokens, index) {
+            index = attribute_end(&tokens, index);
+            continue;
+        }
+        // `Outlet::<Route> {}` is a call to `Outlet`
+        let Some(body) = next_brace(&tokens, index).filter(|body| {
+            tokens[index + 1..*body].iter().all(|token| {
+                matches!(token, TokenTree::Punct(punct) if matches!(punct.as_char(), ':' | '<' | '>'))
+                    || matches!(token, TokenTree::Ident(_))
+            })
+        }) else {
+            index += 1;
+            continue;
+        };
+        let TokenTree::Group(group) = &tokens[body] else {
+            unreachable!()
+        };
+        if name.starts_with(|c: char| c.is_ascii_lowercase()) {
+            if !HTML_ELEMENTS.contains(&name.as_str()) {
+                diagnostics.push(Diagnostic::element(
+                    &tokens[index],
+                    format!("`{name}` is not an HTML element or a component"),
+                ));
+            }
+        } else if let Some(components) = components {
+            if !DIOXUS_COMPONENTS.contains(&name.as_str()) {
+                match components.iter().find(|(component, _)| *component == name) {
+                    Some((_, props)) => {
+                        check_props(&tokens[index], &name, group.stream(), props, diagnostics)
+                    }
+                    None => diagnostics.push(Diagnostic::element(
+                        &tokens[index],
+                        format!("`{name}` is not a declared component"),
+                    )),
+                }
+            }
+        }
+        check_body(group.stream(), components, diagnostics);
+        index = body + 1;
+    }
+}
+
+// Check that the call passes every required prop of the component and no props it does not have
+fn check_props(
+    call: &TokenTree,
+    name: &str,
+    body: TokenStream,
+    props: &[Prop],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let tokens = body.into_iter().collect::<Vec<_>>();
+    let mut passed = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        if is_attribute(&tokens, index) {
+            passed.push(index);
+            index = attribute_end(&tokens, index);
+        } else {
+            index += 1;
+        }
+    }
+    for prop in props {
+        // Children are passed in the body and other elements default to nothing
+        let required = !prop.optional && prop.ty != PropType::Element;
+        let is_passed = passed
+            .iter()
+            .any(|index| tokens[*index].to_string().trim_start_matches("r#") == prop.name);
+        if required && !is_passed {
+            diagnostics.push(Diagnostic::element(
+                call,
+                format!("`{name}` is missing the required prop `{}`", prop.name),
+            ));
+        }
+    }
+    for index in passed {
+        let attribute = tokens[index].to_string();
+        let attribute = attribute.trim_start_matches("r#");
+        if !props.iter().any(|prop| prop.name == attribute) {
+            diagnostics.push(Diagnostic::element(
+                &tokens[index],
+                format!("`{name}` has no prop `{attribute}`"),
+            ));
+        }
+    }
+}
+
+/// The index of the first brace group after the token
+pub(crate) fn next_brace(tokens: &[TokenTree], index: usize) -> Option<usize> {
+    (index + 1..tokens.len()).find(|index| {
+        matches!(&tokens[*index], TokenTree::Group(group) if group.delimiter() == Delimiter::Brace)
+    })
+}
+
+// An identifier followed by a single colon starts an attribute like `class: "p-4"`
+fn is_attribute(tokens: &[TokenTree], index: usize) -> bool {
+    matches!(&tokens[index], TokenTree::Ident(_))
+        && matches!(
+            tokens.get(index + 1),
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':' && punct.spacing() == Spacing::Alone
+        )
+}
+
+/// The index after the comma that ends the attribute starting at the index
+pub(crate) fn attribute_end(tokens: &[TokenTree], index: usize) -> usize {
+    (index + 2..tokens.len())
+        .find(|index| matches!(&tokens[*index], TokenTree::Punct(punct) if punct.as_char() == ','))
+        .map_or(tokens.len(), |comma| comma + 1)
+}
+
+#[test]
+fn validate_generated_code() {
+    let valid = r#"
+#[component]
+pub fn Card(title: String) -> Element {
+    rsx! {
+        div { class: "p-4", "{title}" }
+    }
+}"#;
+    assert_eq!(validate_code(valid), []);
+
+    let keyword_prop = r#"
+#[component]
+pub fn Card(type: String) -> Element {
+    rsx! {
+        div { "{type}" }
+    }
+}"#;
+    let diagnostics = validate_code(keyword_prop);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::Rust);
+    assert_eq!(diagnostics[0].line, 3);
+
+    let invalid_rsx = r#"
+#[component]
+pub fn Card() -> Element {
+    rsx! {
+        div { class: }
+    }
+}"#;
+    let diagnostics = validate_code(invalid_rsx);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::Rsx);
+    assert!(diagnostics[0].is_error());
+
+    let diagnostics = validate_icons(r#"<svg class="w-6 h-6"><!-- spaceship --></svg>"#);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(!diagnostics[0].is_error());
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "icon warning at 0:0: unknown icon \"spaceship\""
+    );
+}
+
+#[test]
+fn validate_elements_and_calls() {
+    let code = r#"
+#[component]
+pub fn app() -> Element {
+    rsx! {
+        div { class: "p-4",
+            nav_bar {}
+            for item in items.iter() {
+                Card { title: "{item}", class: "shadow" }
+            }
+            Footer {}
+            Outlet::<Route> {}
+            Link { to: Route::HomePage {}, "Home" }
+        }
+    }
+}"#;
+    let diagnostics = validate_code(code);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::Element);
+    assert_eq!(
+        diagnostics[0].message,
+        "`nav_bar` is not an HTML element or a component"
+    );
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (6, 12));
+
+    let prop = |name: &str, optional| Prop {
+        name: name.to_string(),
+        ty: PropType::String,
+        optional,
+    };
+    let components = [(
+        "Card".to_string(),
+        vec![
+            prop("title", false),
+            prop("subtitle", false),
+            prop("badge", true),
+        ],
+    )];
+    let messages = validate_calls(code, &components)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "`nav_bar` is not an HTML element or a component",
+            "`Card` is missing the required prop `subtitle`",
+            "`Card` has no prop `class`",
+            "`Footer` is not a declared component",
+        ]
+    );
+}
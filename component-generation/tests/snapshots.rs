@@ -0,0 +1,109 @@
+//! Golden snapshot tests for parsing the model output and converting it to components.
+//!
+//! Each model response in `tests/corpus` is replayed with a [`ScriptedBackend`] and the generated code is compared with the snapshot of the same name in `tests/snapshots`. A missing snapshot fails the test. Run with `UPDATE_SNAPSHOTS=1` to write missing snapshots for new corpus entries or to overwrite the snapshots after an intended change to the generated code.
+
+use component_generation::{
+    DiagnosticKind, OutputGrammar, PartialState, ScriptedBackend, UiGenerator,
+};
+use std::fmt::Write;
+use std::path::Path;
+
+#[tokio::test]
+async fn corpus_snapshots() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+    let mut entries = std::fs::read_dir(root.join("corpus"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert!(!entries.is_empty(), "the corpus is empty");
+
+    let mut mismatched = Vec::new();
+    let mut missing = Vec::new();
+    for path in entries {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let response = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            OutputGrammar::ui().accepts(response.trim_end()),
+            "{name} does not follow the output grammar"
+        );
+        let backend = ScriptedBackend::new().with_response(&response);
+        let state = UiGenerator::new(backend)
+            .generate(&name.replace('-', " "))
+            .await
+            .unwrap();
+        // The app and components may only render declared components and elements from `dioxus_elements`
+        let unknown = state
+            .app_diagnostics()
+            .iter()
+            .chain(
+                state
+                    .components()
+                    .iter()
+                    .flat_map(|component| component.diagnostics()),
+            )
+            .filter(|diagnostic| diagnostic.kind == DiagnosticKind::Element)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert!(
+            unknown.is_empty(),
+            "{name} renders unknown elements: {unknown:?}"
+        );
+        let snapshot = render_snapshot(&state);
+
+        let snapshot_path = root.join("snapshots").join(format!("{name}.snap"));
+        if update {
+            std::fs::create_dir_all(snapshot_path.parent().unwrap()).unwrap();
+            std::fs::write(&snapshot_path, snapshot).unwrap();
+            continue;
+        }
+        match std::fs::read_to_string(&snapshot_path) {
+            Ok(expected) => {
+                if expected != snapshot {
+                    println!("{name} does not match its snapshot. Generated:\n{snapshot}");
+                    mismatched.push(name);
+                }
+            }
+            Err(_) => missing.push(name),
+        }
+    }
+
+    assert!(
+        missing.is_empty(),
+        "snapshots are missing: {missing:?}. Run with UPDATE_SNAPSHOTS=1 to write them"
+    );
+    assert!(
+        mismatched.is_empty(),
+        "snapshots do not match: {mismatched:?}. Run with UPDATE_SNAPSHOTS=1 if the change is intended"
+    );
+}
+
+// Every string the generation produced for the UI, in a stable order
+fn render_snapshot(state: &PartialState) -> String {
+    let mut snapshot = String::new();
+    writeln!(snapshot, "description: {}", state.description).unwrap();
+    writeln!(snapshot, "html: {}", state.html).unwrap();
+    writeln!(snapshot, "\n--- app ---").unwrap();
+    match state.app_component() {
+        Ok(code) => writeln!(snapshot, "{code}").unwrap(),
+        Err(err) => writeln!(snapshot, "error: {err}").unwrap(),
+    }
+    for diagnostic in state.app_diagnostics() {
+        writeln!(snapshot, "diagnostic: {diagnostic}").unwrap();
+    }
+    for component in state.components() {
+        writeln!(snapshot, "\n--- {} ---", component.name).unwrap();
+        writeln!(snapshot, "html: {}", component.html).unwrap();
+        match component.component_string_with(state.components()) {
+            Ok(code) => writeln!(snapshot, "{code}").unwrap(),
+            Err(err) => writeln!(snapshot, "error: {err}").unwrap(),
+        }
+        for diagnostic in component.diagnostics() {
+            writeln!(snapshot, "diagnostic: {diagnostic}").unwrap();
+        }
+    }
+    snapshot
+}